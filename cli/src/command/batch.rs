@@ -0,0 +1,161 @@
+use super::closed;
+use std::collections::BTreeMap;
+use std::io::{stderr, stdout, Write};
+use std::time::Instant;
+use sudokube::{
+    batch::{available_threads, grade_batch, solve_batch, BatchSummary, Graded},
+    collection::{Puzzle, PuzzleCollection},
+    error::SudokuError,
+    format::format_line,
+    model::{Difficulty, Grid},
+    solver::{Backtracking, Uniqueness},
+};
+
+/// puzzles read and solved together per worker thread
+const CHUNK_SIZE_PER_THREAD: usize = 256;
+
+///
+/// Solve every puzzle of the collection at `path` on all CPU cores.
+///
+/// Prints one line per puzzle in input order: its solution (and id), or a comment
+/// line if it has no or multiple solutions or cannot be read. Progress and summary go to stderr,
+/// the batch stops early if the reader of stdout does.
+///
+pub fn solve_batch_file<const N: usize>(path: &str) -> Result<(), String> {
+    run_batch_file::<N>(path, false)
+}
+
+///
+/// Grade every puzzle of the collection at `path` on all CPU cores.
+///
+/// Prints one line per puzzle in input order: the puzzle and its difficulty (and id),
+/// or a comment line as `solve_batch_file` does. Progress and summary go to stderr.
+///
+pub fn grade_batch_file<const N: usize>(path: &str) -> Result<(), String> {
+    run_batch_file::<N>(path, true)
+}
+
+fn run_batch_file<const N: usize>(path: &str, grade: bool) -> Result<(), String> {
+    let mut puzzles = PuzzleCollection::<N>::stream(path).map_err(|error| error.to_string())?;
+    let threads = available_threads();
+    let start = Instant::now();
+    let mut summary = BatchSummary::default();
+    let mut difficulties: BTreeMap<Difficulty, usize> = BTreeMap::new();
+    let mut stdout = stdout().lock();
+    let verb = if grade { "Graded" } else { "Solved" };
+
+    'chunks: loop {
+        let mut chunk: Vec<Result<Puzzle<N>, SudokuError>> = puzzles
+            .by_ref()
+            .take(threads * CHUNK_SIZE_PER_THREAD)
+            .collect();
+        if chunk.is_empty() {
+            break;
+        }
+        // the rest of the file cannot be read, finish the puzzles before
+        let failed = chunk
+            .iter()
+            .position(|puzzle| matches!(puzzle, Err(SudokuError::Io(_))));
+        let failure = failed.map(|i| chunk.drain(i..).next().unwrap());
+
+        let grids: Vec<Grid<N>> = chunk
+            .iter()
+            .filter_map(|puzzle| puzzle.as_ref().ok())
+            .map(|puzzle| puzzle.grid.clone())
+            .collect();
+        let results: Vec<Graded<N>> = if grade {
+            grade_batch(&Backtracking, &grids, threads)
+        } else {
+            solve_batch(&Backtracking, &grids, threads)
+                .into_iter()
+                .map(|solved| Graded {
+                    solved,
+                    difficulty: None,
+                })
+                .collect()
+        };
+        let mut results = results.into_iter();
+
+        for puzzle in &chunk {
+            let puzzle = match puzzle {
+                Ok(puzzle) => puzzle,
+                Err(error) => {
+                    summary.add_error();
+                    let written = writeln!(stdout, "# {}: error: {}", summary.puzzles, error);
+                    if closed(written)? {
+                        break 'chunks;
+                    }
+                    continue;
+                }
+            };
+            let graded = results.next().unwrap();
+            summary.add(&graded.solved);
+            if let Some(difficulty) = graded.difficulty {
+                *difficulties.entry(difficulty).or_default() += 1;
+            }
+
+            let number = summary.puzzles;
+            let line = match (graded.solved.uniqueness, &graded.solved.solution) {
+                (Uniqueness::Unique, Some(solution)) => {
+                    let line = match graded.difficulty {
+                        Some(difficulty) => format!(
+                            "{} {}",
+                            format_line(&puzzle.grid).map_err(|error| error.to_string())?,
+                            difficulty
+                        ),
+                        None => format_line(solution).map_err(|error| error.to_string())?,
+                    };
+                    match &puzzle.metadata.id {
+                        Some(id) => format!("{} {}", line, id),
+                        None => line,
+                    }
+                }
+                (Uniqueness::Multiple, _) => format!("# {}: multiple solutions", number),
+                _ => format!("# {}: no solution", number),
+            };
+            if closed(writeln!(stdout, "{}", line))? {
+                break 'chunks;
+            }
+        }
+
+        summary.elapsed = start.elapsed();
+        eprint!(
+            "\r{} {} puzzles ({:.0}/s)",
+            verb,
+            summary.puzzles,
+            summary.puzzles_per_second()
+        );
+        stderr().flush().map_err(|error| error.to_string())?;
+
+        if let Some(Err(error)) = failure {
+            eprintln!();
+            return Err(error.to_string());
+        }
+    }
+
+    summary.elapsed = start.elapsed();
+    eprintln!();
+    eprintln!(
+        "{} puzzles in {:.2}s ({:.0} puzzles/s) on {} threads",
+        summary.puzzles,
+        summary.elapsed.as_secs_f64(),
+        summary.puzzles_per_second(),
+        threads
+    );
+    eprintln!(
+        "{} unique, {} unsolvable, {} with multiple solutions, {} unreadable",
+        summary.unique, summary.unsolvable, summary.multiple, summary.errors
+    );
+    if grade {
+        let counts: Vec<String> = Difficulty::ALL
+            .iter()
+            .map(|difficulty| {
+                let count = difficulties.get(difficulty).copied().unwrap_or_default();
+                format!("{} {}", count, difficulty)
+            })
+            .collect();
+        eprintln!("{}", counts.join(", "));
+    }
+
+    Ok(())
+}
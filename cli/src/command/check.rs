@@ -0,0 +1,69 @@
+use super::print_output;
+use sudokube::{
+    analysis::{check as check_grid, CheckReport},
+    model::Grid,
+    solver::{Backtracking, Uniqueness},
+};
+
+pub const CHECK_FORMATS: [&str; 2] = ["text", "json"];
+
+/// exit code when the grid has conflicts, incorrect values or givens without a unique solution
+pub const EXIT_INVALID: i32 = 2;
+
+/// Print the check report of `grid` in `format`, returns whether the grid is valid
+pub fn check<const N: usize>(grid: &Grid<N>, format: &str) -> Result<bool, String> {
+    let report = check_grid(&Backtracking, grid);
+    let output = match format {
+        "json" => format!("{}\n", report.to_json()),
+        "text" => report_text(grid, &report),
+        _ => return Err(format!("Unsupported output format {}", format)),
+    };
+    print_output(&output)?;
+
+    let correct = matches!(&report.incorrect, Some(incorrect) if incorrect.is_empty());
+    Ok(report.conflicts.is_empty() && correct)
+}
+
+fn report_text<const N: usize>(grid: &Grid<N>, report: &CheckReport) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if report.conflicts.is_empty() {
+        lines.push(String::from("Conflicts: none"));
+    } else {
+        lines.push(format!("Conflicts: {}", report.conflicts.len()));
+        for [coor1, coor2] in &report.conflicts {
+            let value = grid.get_cell(*coor1).value.unwrap_or_default() + 1;
+            lines.push(format!("  {} and {} both have {}", coor1, coor2, value));
+        }
+    }
+
+    lines.push(format!(
+        "Complete: {}",
+        if report.complete { "yes" } else { "no" }
+    ));
+
+    let givens = match report.givens {
+        Uniqueness::None => "no solution",
+        Uniqueness::Unique => "unique solution",
+        Uniqueness::Multiple => "multiple solutions",
+    };
+    lines.push(format!("Givens: {}", givens));
+
+    let solution = match &report.incorrect {
+        None => String::from("unknown"),
+        Some(incorrect) if incorrect.is_empty() => match report.complete {
+            true => String::from("solved"),
+            false => String::from("all values correct so far"),
+        },
+        Some(incorrect) => {
+            let cells: Vec<String> = incorrect.iter().map(|c| c.to_string()).collect();
+            format!(
+                "{} incorrect values at {}",
+                incorrect.len(),
+                cells.join(", ")
+            )
+        }
+    };
+    lines.push(format!("Solution: {}", solution));
+
+    format!("{}\n", lines.join("\n"))
+}
@@ -0,0 +1,388 @@
+use super::print_output;
+use crate::game::Game;
+use std::fs;
+use sudokube::{
+    collection::{CollectionFormat, Puzzle, PuzzleMetadata, PuzzleReader, PuzzleWriter},
+    format::{
+        decode_share_code, encode_share_code, format_fpuzzles, parse_fpuzzles, FPuzzleInfo,
+        FileFormat,
+    },
+    model::{Grid, Notes},
+};
+
+pub const CONVERT_FORMATS: [&str; 8] = [
+    "json", "line", "ss", "sdk", "sdm", "jsonl", "fpuzzles", "share",
+];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Format {
+    File(FileFormat),
+    JsonLines, // collection with metadata, see `CollectionFormat::JsonLines`
+}
+
+impl Format {
+    fn from_name(name: &str) -> Result<Format, String> {
+        let format = match name {
+            "json" => Format::File(FileFormat::Json),
+            "line" => Format::File(FileFormat::Line),
+            "ss" => Format::File(FileFormat::SimpleSudoku),
+            "sdk" => Format::File(FileFormat::SadMan),
+            "sdm" => Format::File(FileFormat::SadManMulti),
+            "jsonl" => Format::JsonLines,
+            "fpuzzles" => Format::File(FileFormat::FPuzzles),
+            "share" => Format::File(FileFormat::ShareCode),
+            _ => return Err(format!("Unknown format {}", name)),
+        };
+        Ok(format)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Format::File(FileFormat::Json) => "json",
+            Format::File(FileFormat::Line) => "line",
+            Format::File(FileFormat::SimpleSudoku) => "ss",
+            Format::File(FileFormat::SadMan) => "sdk",
+            Format::File(FileFormat::SadManMulti) => "sdm",
+            Format::JsonLines => "jsonl",
+            Format::File(FileFormat::FPuzzles) => "fpuzzles",
+            Format::File(FileFormat::ShareCode) => "share",
+        }
+    }
+
+    /// from the extension of `path`, `.jsonl` / `.ndjson` being collections
+    fn from_path(path: &str) -> Option<Format> {
+        if CollectionFormat::from_path(path) == CollectionFormat::JsonLines {
+            return Some(Format::JsonLines);
+        }
+        FileFormat::from_extension(path).map(Format::File)
+    }
+
+    fn is_multi(&self) -> bool {
+        match self {
+            Format::File(format) => format.is_multi(),
+            Format::JsonLines => true,
+        }
+    }
+
+    /// whether player entries are kept apart from givens
+    fn keeps_entries(&self) -> bool {
+        matches!(
+            self,
+            Format::File(FileFormat::Json)
+                | Format::File(FileFormat::FPuzzles)
+                | Format::File(FileFormat::ShareCode)
+        )
+    }
+
+    /// metadata left after writing in this format
+    fn kept_metadata(&self, metadata: &PuzzleMetadata) -> PuzzleMetadata {
+        match self {
+            Format::JsonLines => metadata.clone(),
+            Format::File(FileFormat::Line) => PuzzleMetadata {
+                id: metadata.id.clone(),
+                ..PuzzleMetadata::default()
+            },
+            Format::File(FileFormat::FPuzzles) => PuzzleMetadata {
+                title: metadata.title.clone(),
+                author: metadata.author.clone(),
+                ..PuzzleMetadata::default()
+            },
+            _ => PuzzleMetadata::default(),
+        }
+    }
+}
+
+/// puzzle with the information only some formats can hold
+struct Entry<const N: usize> {
+    puzzle: Puzzle<N>,
+    notes: Option<Notes<N>>,
+    constraints: Vec<String>, // variant constraints that are not part of `puzzle`
+}
+
+impl<const N: usize> Entry<N> {
+    fn new(grid: Grid<N>) -> Entry<N> {
+        Entry {
+            puzzle: Puzzle::new(grid),
+            notes: None,
+            constraints: Vec::new(),
+        }
+    }
+}
+
+///
+/// Convert the puzzle file or collection at `input` to `output` ("-" for stdout).
+/// Formats are inferred from the file extensions (or content of `input`) unless given.
+/// Warns about notes, metadata and constraints the target format cannot hold.
+///
+pub fn convert<const N: usize>(
+    input: &str,
+    output: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<(), String> {
+    let content = fs::read_to_string(input)
+        .map_err(|error| format!("Error reading file {}: {}", input, error))?;
+    let from = match from {
+        Some(name) => Format::from_name(name)?,
+        None => {
+            Format::from_path(input).unwrap_or_else(|| Format::File(FileFormat::sniff(&content)))
+        }
+    };
+    let to = match to {
+        Some(name) => Format::from_name(name)?,
+        None => Format::from_path(output).unwrap_or(Format::File(FileFormat::Line)),
+    };
+
+    let entries: Vec<Entry<N>> =
+        read(from, &content).map_err(|error| format!("{} ({} format)", error, from.name()))?;
+    if !to.is_multi() && entries.len() != 1 {
+        return Err(format!(
+            "{} format holds exactly one puzzle, {} has {}",
+            to.name(),
+            input,
+            entries.len()
+        ));
+    }
+
+    warn_losses(&entries, to);
+    let content = write(&entries, to)?;
+    if output == "-" {
+        print_output(&content)
+    } else {
+        fs::write(output, content).map_err(|error| error.to_string())
+    }
+}
+
+fn read<const N: usize>(format: Format, content: &str) -> Result<Vec<Entry<N>>, String> {
+    let collection = |format: CollectionFormat| -> Result<Vec<Entry<N>>, String> {
+        PuzzleReader::<&[u8], N>::new(content.as_bytes(), format)
+            .map(|puzzle| {
+                let puzzle = puzzle.map_err(|error| error.to_string())?;
+                Ok(Entry {
+                    puzzle,
+                    notes: None,
+                    constraints: Vec::new(),
+                })
+            })
+            .collect()
+    };
+
+    let grids = |format: FileFormat| -> Result<Vec<Entry<N>>, String> {
+        Ok(format
+            .parse(content)
+            .map_err(|error| error.to_string())?
+            .into_iter()
+            .map(Entry::new)
+            .collect())
+    };
+
+    match format {
+        Format::JsonLines => collection(CollectionFormat::JsonLines),
+        Format::File(FileFormat::Line) => collection(CollectionFormat::Lines),
+        Format::File(FileFormat::FPuzzles) => {
+            let fpuzzle = parse_fpuzzles(content).map_err(|error| error.to_string())?;
+            let mut entry = Entry::new(fpuzzle.grid);
+            entry.puzzle.metadata.title = fpuzzle.info.title;
+            entry.puzzle.metadata.author = fpuzzle.info.author;
+            entry.constraints = fpuzzle.unsupported;
+            Ok(vec![entry])
+        }
+        Format::File(FileFormat::ShareCode) => {
+            let shared = decode_share_code(content).map_err(|error| error.to_string())?;
+            let mut entry = Entry::new(shared.grid);
+            entry.notes = shared.notes;
+            Ok(vec![entry])
+        }
+        // a game saved by `play` or `make`, otherwise a plain grid
+        Format::File(FileFormat::Json) => match Game::from_json(content) {
+            Ok(game) => {
+                let mut entry = Entry::new(game.grid);
+                if !game.notes.is_empty() {
+                    entry.notes = Some(game.notes);
+                }
+                Ok(vec![entry])
+            }
+            Err(_) => grids(FileFormat::Json),
+        },
+        Format::File(format) => grids(format),
+    }
+}
+
+fn write<const N: usize>(entries: &[Entry<N>], format: Format) -> Result<String, String> {
+    let collection = |format: CollectionFormat| -> Result<String, String> {
+        let mut writer = PuzzleWriter::new(Vec::new(), format);
+        for entry in entries {
+            writer
+                .write(&entry.puzzle)
+                .map_err(|error| error.to_string())?;
+        }
+        String::from_utf8(writer.into_inner()).map_err(|error| error.to_string())
+    };
+
+    let result = match format {
+        Format::JsonLines => return collection(CollectionFormat::JsonLines),
+        Format::File(FileFormat::Line) => return collection(CollectionFormat::Lines),
+        Format::File(FileFormat::FPuzzles) => {
+            let metadata = &entries[0].puzzle.metadata;
+            let info = FPuzzleInfo {
+                title: metadata.title.clone(),
+                author: metadata.author.clone(),
+                ruleset: None,
+            };
+            format_fpuzzles(&entries[0].puzzle.grid, &info, false)
+        }
+        Format::File(FileFormat::ShareCode) => {
+            encode_share_code(&entries[0].puzzle.grid, entries[0].notes.as_ref())
+        }
+        Format::File(format) => {
+            let grids: Vec<Grid<N>> = entries
+                .iter()
+                .map(|entry| entry.puzzle.grid.clone())
+                .collect();
+            format.format(&grids)
+        }
+    };
+
+    let mut content = result.map_err(|error| error.to_string())?;
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+fn warn_losses<const N: usize>(entries: &[Entry<N>], to: Format) {
+    let has_notes = |entry: &Entry<N>| matches!(&entry.notes, Some(notes) if !notes.is_empty());
+    if to != Format::File(FileFormat::ShareCode) && entries.iter().any(has_notes) {
+        eprintln!("Warning: notes are not kept in {} format", to.name());
+    }
+
+    let lost_metadata = entries
+        .iter()
+        .filter(|entry| to.kept_metadata(&entry.puzzle.metadata) != entry.puzzle.metadata)
+        .count();
+    if lost_metadata > 0 {
+        eprintln!(
+            "Warning: metadata of {} puzzles is not (fully) kept in {} format",
+            lost_metadata,
+            to.name()
+        );
+    }
+
+    let has_entries = |entry: &Entry<N>| {
+        entry.puzzle.grid.rows.iter().any(|row| {
+            row.cells
+                .iter()
+                .any(|cell| cell.editable && cell.value.is_some())
+        })
+    };
+    if !to.keeps_entries() && entries.iter().any(has_entries) {
+        eprintln!(
+            "Warning: player entries become givens in {} format",
+            to.name()
+        );
+    }
+
+    for entry in entries {
+        for constraint in &entry.constraints {
+            eprintln!("Warning: variant constraint is dropped: {}", constraint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudokube::{
+        format::{format_line, parse_line},
+        model::Coordinate,
+    };
+
+    const PUZZLE: &str = "12............43";
+    const OTHER: &str = "...4............";
+
+    fn entry(line: &str) -> Entry<4> {
+        let mut entry = Entry::new(parse_line(line).unwrap());
+        entry.puzzle.metadata = PuzzleMetadata {
+            id: Some(String::from("7")),
+            title: Some(String::from("Small")),
+            author: Some(String::from("Someone")),
+            ..PuzzleMetadata::default()
+        };
+        entry
+    }
+
+    fn lines(entries: &[Entry<4>]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|entry| format_line(&entry.puzzle.grid).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn formats_by_name_and_path() {
+        for name in CONVERT_FORMATS {
+            assert_eq!(Format::from_name(name).unwrap().name(), name);
+        }
+        assert!(Format::from_name("csv").is_err());
+        assert_eq!(Format::from_path("a.jsonl"), Some(Format::JsonLines));
+        assert_eq!(Format::from_path("a.ndjson"), Some(Format::JsonLines));
+        assert_eq!(
+            Format::from_path("a.sdk"),
+            Some(Format::File(FileFormat::SadMan))
+        );
+        assert_eq!(Format::from_path("a.txt"), None);
+    }
+
+    #[test]
+    fn round_trips_through_every_format() {
+        for name in CONVERT_FORMATS {
+            let format = Format::from_name(name).unwrap();
+            let content = write(&[entry(PUZZLE)], format).unwrap();
+            assert!(content.ends_with('\n'), "{}", name);
+            let entries = read::<4>(format, &content).unwrap();
+            assert_eq!(lines(&entries), [PUZZLE], "{}", name);
+            assert_eq!(
+                entries[0].puzzle.metadata,
+                format.kept_metadata(&entry(PUZZLE).puzzle.metadata),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn collections_keep_every_puzzle() {
+        let entries = [entry(PUZZLE), entry(OTHER)];
+        for format in [
+            Format::JsonLines,
+            Format::File(FileFormat::Line),
+            Format::File(FileFormat::SadManMulti),
+        ] {
+            let content = write(&entries, format).unwrap();
+            assert_eq!(lines(&read(format, &content).unwrap()), [PUZZLE, OTHER]);
+        }
+    }
+
+    #[test]
+    fn reads_saved_games_with_notes() {
+        let mut game: Game<4> = Game::new(parse_line(PUZZLE).unwrap());
+        game.enter(Coordinate(0, 2), Some(2));
+        game.notes.set(Coordinate(0, 3), &[3]);
+
+        let entries = read::<4>(Format::File(FileFormat::Json), &game.to_json()).unwrap();
+        assert_eq!(lines(&entries), ["123...........43"]);
+        assert!(entries[0].puzzle.grid.get_cell(Coordinate(0, 2)).editable);
+        assert!(entries[0].notes.as_ref().unwrap().has(Coordinate(0, 3), 3));
+
+        // notes are kept in share codes
+        let share = Format::File(FileFormat::ShareCode);
+        let shared = read::<4>(share, &write(&entries, share).unwrap()).unwrap();
+        assert!(shared[0].notes.as_ref().unwrap().has(Coordinate(0, 3), 3));
+
+        // plain grids are read too
+        let grid: Grid<4> = parse_line(PUZZLE).unwrap();
+        let entries = read::<4>(Format::File(FileFormat::Json), &grid.to_json()).unwrap();
+        assert_eq!(lines(&entries), [PUZZLE]);
+        assert!(entries[0].notes.is_none());
+    }
+}
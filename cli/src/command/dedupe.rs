@@ -0,0 +1,63 @@
+use super::print_output;
+use std::collections::HashMap;
+use sudokube::{collection::PuzzleCollection, format::format_line, transform::canonicalize};
+
+/// puzzles that are the same up to symmetry, keyed by their canonical form
+struct EquivalenceClass {
+    canonical: String,
+    members: Vec<String>, // position (and id) of each puzzle in the collection
+}
+
+///
+/// Print the equivalence classes of puzzles in the collection at `path`,
+/// only classes with more than one puzzle unless `all`.
+///
+pub fn dedupe<const N: usize>(path: &str, all: bool) -> Result<(), String> {
+    let puzzles = PuzzleCollection::<N>::stream(path).map_err(|error| error.to_string())?;
+
+    let mut classes: Vec<EquivalenceClass> = Vec::new();
+    let mut class_index: HashMap<String, usize> = HashMap::new();
+    let mut count = 0;
+
+    for (i, puzzle) in puzzles.enumerate() {
+        let puzzle = puzzle.map_err(|error| error.to_string())?;
+        let canonical = canonicalize(&puzzle.grid).map_err(|error| error.to_string())?;
+        let canonical = format_line(&canonical.grid).map_err(|error| error.to_string())?;
+
+        let member = match &puzzle.metadata.id {
+            Some(id) => format!("#{} ({})", i + 1, id),
+            None => format!("#{}", i + 1),
+        };
+
+        match class_index.get(&canonical) {
+            Some(index) => classes[*index].members.push(member),
+            None => {
+                class_index.insert(canonical.clone(), classes.len());
+                classes.push(EquivalenceClass {
+                    canonical,
+                    members: vec![member],
+                });
+            }
+        }
+        count += 1;
+    }
+
+    let duplicated = classes.iter().filter(|c| c.members.len() > 1).count();
+    let mut output = format!(
+        "{} puzzles, {} distinct, {} classes with duplicates\n",
+        count,
+        classes.len(),
+        duplicated
+    );
+
+    for class in classes.iter().filter(|c| all || c.members.len() > 1) {
+        output.push_str(&format!(
+            "\n{} ({} puzzles)\n  {}\n",
+            class.canonical,
+            class.members.len(),
+            class.members.join(", ")
+        ));
+    }
+
+    print_output(&output)
+}
@@ -0,0 +1,23 @@
+use super::print_output;
+use std::fs;
+use sudokube::{format::format_dimacs, model::Grid};
+
+/// formats that a game can be exported to, but not loaded from
+pub const EXPORT_FORMATS: [&str; 1] = ["dimacs"];
+
+/// Write `grid` in export `format` to `output`, or stdout if `None`
+pub fn export<const N: usize>(
+    grid: &Grid<N>,
+    format: &str,
+    output: Option<&str>,
+) -> Result<(), String> {
+    let content = match format {
+        "dimacs" => format_dimacs(grid).map_err(|error| error.to_string())?,
+        _ => return Err(format!("Unsupported export format {}", format)),
+    };
+
+    match output {
+        Some(path) => fs::write(path, content).map_err(|error| error.to_string()),
+        None => print_output(&content),
+    }
+}
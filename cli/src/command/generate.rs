@@ -0,0 +1,86 @@
+use super::closed;
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use sudokube::{
+    collection::{CollectionFormat, Puzzle, PuzzleMetadata, PuzzleWriter},
+    generator::{Generator, GeneratorOptions},
+};
+
+pub const GENERATE_SIZES: [&str; 3] = ["4", "9", "16"];
+pub const COLLECTION_FORMATS: [&str; 2] = ["lines", "jsonl"];
+
+///
+/// Generate `count` puzzles and write them as a collection to `output`,
+/// or stdout if `None`, one at a time as they are generated until the reader of stdout stops.
+///
+pub fn generate<const N: usize>(
+    options: GeneratorOptions,
+    count: usize,
+    seed: u64,
+    format: CollectionFormat,
+    output: Option<&str>,
+) -> Result<(), String> {
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).map_err(|error| error.to_string())?,
+        )),
+        None => Box::new(stdout().lock()),
+    };
+    let mut generator = Generator::from_seed(seed, options);
+
+    for i in 0..count {
+        let generated = generator
+            .generate::<N>()
+            .map_err(|error| error.to_string())?;
+        let puzzle = Puzzle {
+            grid: generated.puzzle,
+            metadata: PuzzleMetadata {
+                id: Some(format!("{}-{}", seed, i + 1)),
+                difficulty: Some(generated.difficulty.to_string()),
+                ..PuzzleMetadata::default()
+            },
+        };
+        // formatted apart so errors of the output keep their kind
+        let mut line = PuzzleWriter::new(Vec::new(), format);
+        line.write(&puzzle).map_err(|error| error.to_string())?;
+        let written = writer
+            .write_all(&line.into_inner())
+            .and_then(|()| writer.flush());
+        if closed(written)? {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// `format` if given, otherwise inferred from the extension of `output`
+pub fn collection_format(format: Option<&str>, output: Option<&str>) -> CollectionFormat {
+    match (format, output) {
+        (Some("jsonl"), _) => CollectionFormat::JsonLines,
+        (Some(_), _) => CollectionFormat::Lines,
+        (None, Some(path)) => CollectionFormat::from_path(path),
+        (None, None) => CollectionFormat::Lines,
+    }
+}
+
+/// `a..b` or a single value `a` (same as `a..a`)
+pub fn parse_range<T: FromStr + Copy>(arg: &str) -> Result<RangeInclusive<T>, String>
+where
+    T::Err: std::fmt::Display,
+{
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<T>()
+            .map_err(|error| format!("Invalid range {}: {}", arg, error))
+    };
+    match arg.split_once("..") {
+        Some((start, end)) => Ok(parse(start)?..=parse(end)?),
+        None => {
+            let value = parse(arg)?;
+            Ok(value..=value)
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::config::Config;
+use crate::display::DGrid;
+use crate::enums::RenderVariant;
+use crate::game::Game;
+use sudokube::{
+    model::Coordinate,
+    solver::logical::{next_step, Candidates, House, Step},
+};
+
+///
+/// Next logical step of a game that places a value, revealed one stage per hint key press:
+/// the houses to look at, the technique, the cells of the pattern with the value shown in
+/// its cell and finally the value entered.
+/// Play does not show notes, so steps that only eliminate candidates are not hints of their
+/// own but lead up to the step placing a value, and are named along with it.
+///
+pub struct Hint {
+    steps: Vec<Step>, // eliminations, then the step placing values
+    stage: u8,
+}
+
+impl Hint {
+    /// easiest steps to a value from the current entries and notes, `None` if stuck or solved
+    pub fn find<const N: usize>(game: &Game<N>) -> Option<Hint> {
+        let mut candidates = candidates(game);
+        let mut steps = Vec::new();
+        loop {
+            let step = next_step(&candidates)?;
+            let places = !step.placements.is_empty();
+            candidates.apply(&step);
+            steps.push(step);
+            if places {
+                return Some(Hint { steps, stage: 0 });
+            }
+        }
+    }
+
+    /// reveal the next stage, returns whether the step was applied
+    pub fn reveal<const N: usize>(
+        &mut self,
+        game: &mut Game<N>,
+        d_grid: &mut DGrid<N>,
+        config: &Config,
+    ) -> bool {
+        self.stage += 1;
+        let (step, before) = self.steps.split_last().unwrap();
+        let houses = self.houses();
+
+        // clear highlights of the previous stage
+        d_grid.render(&game.grid, config);
+        d_grid.rerender(&game.grid, config);
+
+        match self.stage {
+            1 => {
+                let coordinates: Vec<Coordinate> = houses
+                    .iter()
+                    .flat_map(|house| house.coordinates::<N>())
+                    .collect();
+                d_grid.highlight(
+                    &game.grid,
+                    config,
+                    &coordinates,
+                    RenderVariant::DirectionalRelative,
+                );
+                d_grid.render_status(&format!("Hint: look at {}", houses_text(&houses)));
+                false
+            }
+            2 => {
+                d_grid.render_status(&format!(
+                    "Hint: {} in {}",
+                    self.techniques(),
+                    houses_text(&houses)
+                ));
+                false
+            }
+            3 => {
+                let cells: Vec<Coordinate> = self
+                    .steps
+                    .iter()
+                    .flat_map(|step| step.cells.iter().copied())
+                    .collect();
+                d_grid.highlight(&game.grid, config, &cells, RenderVariant::SameValue);
+                for (coordinate, value) in &step.placements {
+                    d_grid.preview(&game.grid, config, *coordinate, *value);
+                }
+                let mut status = format!(
+                    "Hint: {} on {}, {}",
+                    step.technique,
+                    values(config, &step.values),
+                    effect(config, step)
+                );
+                if !before.is_empty() {
+                    let effects: Vec<String> = before
+                        .iter()
+                        .map(|step| format!("{} {}", step.technique, effect(config, step)))
+                        .collect();
+                    status.push_str(&format!(" once {}", effects.join(", then ")));
+                }
+                d_grid.render_status(&status);
+                false
+            }
+            _ => {
+                apply(game, d_grid, config, step);
+                d_grid.render_status(&format!(
+                    "Hint: {} applied, {}",
+                    step.technique,
+                    effect(config, step)
+                ));
+                true
+            }
+        }
+    }
+
+    /// houses of all steps, in order of their first use
+    fn houses(&self) -> Vec<House> {
+        let mut houses: Vec<House> = Vec::new();
+        for house in self.steps.iter().flat_map(|step| step.houses.iter()) {
+            if !houses.contains(house) {
+                houses.push(*house);
+            }
+        }
+        houses
+    }
+
+    /// techniques of the steps in order, e.g. `Pointing, then Hidden Single`
+    fn techniques(&self) -> String {
+        let mut techniques: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| step.technique.to_string())
+            .collect();
+        techniques.dedup();
+        techniques.join(", then ")
+    }
+}
+
+/// candidates of the game grid, restricted to the notes of cells that have some
+fn candidates<const N: usize>(game: &Game<N>) -> Candidates<N> {
+    let mut candidates = Candidates::from_grid(&game.grid);
+    for coordinate in coordinates::<N>() {
+        let mask = game.notes.mask(coordinate);
+        if mask != 0 {
+            candidates.restrict(coordinate, mask);
+        }
+    }
+    candidates
+}
+
+/// enter the placements of `step`
+fn apply<const N: usize>(game: &mut Game<N>, d_grid: &mut DGrid<N>, config: &Config, step: &Step) {
+    for (coordinate, value) in &step.placements {
+        if let Some(entry) = game.enter(*coordinate, Some(*value)) {
+            d_grid.set_value(&game.grid, config, entry.coordinate, entry.from, entry.to);
+        }
+    }
+}
+
+fn coordinates<const N: usize>() -> Vec<Coordinate> {
+    (0..N as u8)
+        .flat_map(|row| (0..N as u8).map(move |col| Coordinate(row, col)))
+        .collect()
+}
+
+fn houses_text(houses: &[House]) -> String {
+    houses
+        .iter()
+        .map(|house| house.to_string())
+        .collect::<Vec<String>>()
+        .join(" and ")
+}
+
+fn values(config: &Config, values: &[u8]) -> String {
+    let value_map = config.value_map();
+    values
+        .iter()
+        .map(|value| value_map.get(value).cloned().unwrap_or_default())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// placements and eliminations of `step`, e.g. `r1c2 is 5` or `removes 4 from r2c3, r2c5`
+fn effect(config: &Config, step: &Step) -> String {
+    let mut effects: Vec<String> = step
+        .placements
+        .iter()
+        .map(|(coordinate, value)| format!("{} is {}", coordinate, values(config, &[*value])))
+        .collect();
+
+    let mut removed: Vec<u8> = step.eliminations.iter().map(|(_, value)| *value).collect();
+    removed.sort_unstable();
+    removed.dedup();
+    for value in removed {
+        let cells: Vec<String> = step
+            .eliminations
+            .iter()
+            .filter(|(_, v)| *v == value)
+            .map(|(coordinate, _)| coordinate.to_string())
+            .collect();
+        effects.push(format!(
+            "removes {} from {}",
+            values(config, &[value]),
+            cells.join(", ")
+        ));
+    }
+
+    effects.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudokube::{
+        format::{format_line, parse_line},
+        solver::logical::Technique,
+    };
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    #[test]
+    fn steps_agree_with_the_solution() {
+        let solution: Game<9> = Game::new(parse_line(SOLUTION).unwrap());
+        let mut game: Game<9> = Game::new(parse_line(PUZZLE).unwrap());
+        while let Some(hint) = Hint::find(&game) {
+            let (step, before) = hint.steps.split_last().unwrap();
+            assert!(!step.placements.is_empty());
+            assert!(before.iter().all(|step| step.placements.is_empty()));
+            for step in &hint.steps {
+                for (coordinate, value) in &step.eliminations {
+                    assert_ne!(solution.grid.get_cell(*coordinate).value, Some(*value));
+                }
+            }
+            // only placements are entered, play keeps no notes
+            for (coordinate, value) in &step.placements {
+                assert_eq!(solution.grid.get_cell(*coordinate).value, Some(*value));
+                game.enter(*coordinate, Some(*value));
+            }
+        }
+        assert_eq!(format_line(&game.grid).unwrap(), SOLUTION);
+    }
+
+    #[test]
+    fn names_every_step_of_a_hint() {
+        let hint = Hint {
+            steps: vec![
+                Step {
+                    technique: Technique::Pointing,
+                    houses: vec![House::SubGrid(0), House::Row(0)],
+                    cells: vec![Coordinate(0, 0), Coordinate(0, 1)],
+                    values: vec![2],
+                    placements: vec![],
+                    eliminations: vec![(Coordinate(0, 5), 2)],
+                },
+                Step {
+                    technique: Technique::HiddenSingle,
+                    houses: vec![House::Row(0)],
+                    cells: vec![Coordinate(0, 4)],
+                    values: vec![2],
+                    placements: vec![(Coordinate(0, 4), 2)],
+                    eliminations: vec![],
+                },
+            ],
+            stage: 0,
+        };
+        assert_eq!(houses_text(&hint.houses()), "box 1 and row 1");
+        assert_eq!(hint.techniques(), "Pointing, then Hidden Single");
+    }
+
+    #[test]
+    fn candidates_follow_the_notes() {
+        let mut game: Game<4> = Game::new(parse_line("12............43").unwrap());
+        assert_eq!(candidates(&game).get(Coordinate(0, 2)), vec![2]);
+        assert_eq!(candidates(&game).get(Coordinate(1, 2)), vec![0, 1, 2]);
+        game.notes.set(Coordinate(1, 2), &[1, 2]);
+        assert_eq!(candidates(&game).get(Coordinate(1, 2)), vec![1, 2]);
+        // notes do not bring back candidates ruled out by entries
+        game.notes.set(Coordinate(1, 2), &[0, 3]);
+        assert_eq!(candidates(&game).get(Coordinate(1, 2)), vec![0]);
+    }
+
+    #[test]
+    fn describes_steps() {
+        let config = Config::default();
+        let step = Step {
+            technique: Technique::NakedPair,
+            houses: vec![House::Row(1), House::SubGrid(3)],
+            cells: vec![Coordinate(1, 0), Coordinate(1, 1)],
+            values: vec![0, 3],
+            placements: vec![(Coordinate(0, 1), 4)],
+            eliminations: vec![
+                (Coordinate(1, 2), 3),
+                (Coordinate(3, 3), 0),
+                (Coordinate(1, 4), 3),
+            ],
+        };
+        assert_eq!(houses_text(&step.houses), "row 2 and box 4");
+        assert_eq!(values(&config, &step.values), "1,4");
+        assert_eq!(
+            effect(&config, &step),
+            "r1c2 is 5, removes 1 from r4c4, removes 4 from r2c3, r2c5"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use super::play::{key_value, render_board};
+use crate::config::Config;
+use crate::display::{DGrid, Session};
+use crate::enums::{Navigation, RenderVariant};
+use crate::game::{Game, Mode, Recovery, AUTOSAVE_INTERVAL};
+use crossterm::event::{poll, read, Event, KeyCode, KeyEvent};
+use sudokube::{
+    analysis::{conflicts, find_ambiguity_within, find_contradiction_within},
+    model::{Coordinate, Grid},
+    solver::{Backtracking, Uniqueness},
+};
+
+/// cells listed in the status line when revealing where solutions differ
+const REVEALED_CELLS: usize = 6;
+/// search nodes spent counting solutions after each change, so typing stays responsive
+const VALIDITY_BUDGET: usize = 50_000;
+/// search nodes spent diagnosing or revealing solutions on request, so the editor cannot hang
+const ANALYSIS_BUDGET: usize = 2_000_000;
+/// status when a search on request runs out of nodes
+const UNKNOWN: &str = "Solutions unknown, try with more givens";
+
+///
+/// Create or edit the grid of `game` in the terminal until the author quits.
+/// The game is written to `save_path` on the save key, and on quit if confirmed.
+///
+pub fn make<const N: usize>(
+    mut game: Game<N>,
+    save_path: Option<&str>,
+    config: &Config,
+) -> Result<(), String> {
+    let mut config = game.config(config);
+    let config = &mut config;
+    let mut d_grid: DGrid<N> = DGrid::new(&game.grid, Coordinate(1, 2));
+    let mut saved = true;
+
+    let session = Session::start();
+    d_grid.active = Coordinate(N as u8 / 2, N as u8 / 2);
+    render_board(&mut d_grid, &game.grid, config);
+    render_validity(&d_grid, &game.grid);
+
+    let key_binding = config.key_binding();
+    let navigation_key_events = key_binding.navigation();
+
+    loop {
+        if !poll(AUTOSAVE_INTERVAL).unwrap() {
+            Recovery::save(Mode::Make, save_path, &game);
+            continue;
+        }
+        let event = read().unwrap();
+        let grid = &mut game.grid;
+
+        // navigation event;
+        if event == navigation_key_events.left().crossterm() {
+            d_grid.navigate(grid, config, Navigation::Col(-1));
+        } else if event == navigation_key_events.up().crossterm() {
+            d_grid.navigate(grid, config, Navigation::Row(-1));
+        } else if event == navigation_key_events.right().crossterm() {
+            d_grid.navigate(grid, config, Navigation::Col(1));
+        } else if event == navigation_key_events.down().crossterm() {
+            d_grid.navigate(grid, config, Navigation::Row(1));
+        } else if event == navigation_key_events.next_group().crossterm() {
+            d_grid.navigate(grid, config, Navigation::Group(1));
+        } else if event == navigation_key_events.previous_group().crossterm() {
+            d_grid.navigate(grid, config, Navigation::Group(-1));
+        } else if event == key_binding.quit().crossterm() {
+            if saved {
+                break;
+            }
+            match save_path {
+                Some(path) => match ask(&d_grid, &format!("Save to {}? (y/n/c)", path), "ync") {
+                    'y' => {
+                        game.write_grid(path)?;
+                        break;
+                    }
+                    'n' => break,
+                    _ => d_grid.render_status(""),
+                },
+                None => match ask(&d_grid, "Quit without saving? (y/n)", "yn") {
+                    'y' => break,
+                    _ => d_grid.render_status(""),
+                },
+            }
+        } else if event == key_binding.save().crossterm() {
+            match save_path {
+                Some(path) => match game.write_grid(path) {
+                    Ok(()) => {
+                        saved = true;
+                        d_grid.render_status(&format!("Saved to {}", path));
+                    }
+                    Err(error) => d_grid.render_status(&error),
+                },
+                None => d_grid.render_status("No file to save to, start make with a path"),
+            }
+        } else if event == key_binding.lock_givens().crossterm() {
+            let count = lock_givens(grid);
+            render_board(&mut d_grid, grid, config);
+            d_grid.render_status(&format!("Locked {} values as givens", count));
+            saved = false;
+            Recovery::save(Mode::Make, save_path, &game);
+        } else if event == key_binding.suspend().crossterm() {
+            session.suspend();
+            render_board(&mut d_grid, grid, config);
+            render_validity(&d_grid, grid);
+        } else if event == key_binding.toggle_context_highlight().crossterm() {
+            config.toggle_context_highlight();
+            d_grid.rerender(grid, config);
+        } else if event == key_binding.delete().crossterm() {
+            // a cleared given is no longer one
+            let old_value = grid.get_cell(d_grid.active).value;
+            grid.set_cell_value(d_grid.active, None).unwrap();
+            grid.set_cell_editable(d_grid.active, true).unwrap();
+            d_grid.set_value(grid, config, d_grid.active, old_value, None);
+            render_validity(&d_grid, grid);
+            saved = false;
+            Recovery::save(Mode::Make, save_path, &game);
+        } else if event == key_binding.diagnose().crossterm() {
+            d_grid.render(grid, config);
+            d_grid.rerender(grid, config);
+            match find_contradiction_within(grid, ANALYSIS_BUDGET) {
+                Some(Some(contradiction)) => {
+                    let mut cells = contradiction.cells.clone();
+                    cells.extend(contradiction.empty_cell);
+                    d_grid.highlight(grid, config, &cells, RenderVariant::Error);
+                    d_grid.render_status(&format!(
+                        "No solution: {} highlighted values cannot all be kept",
+                        contradiction.cells.len()
+                    ));
+                }
+                Some(None) => d_grid.render_status("Grid has a solution"),
+                None => d_grid.render_status(UNKNOWN),
+            }
+        } else if event == key_binding.reveal_solutions().crossterm() {
+            d_grid.render(grid, config);
+            d_grid.rerender(grid, config);
+            match find_ambiguity_within(grid, ANALYSIS_BUDGET) {
+                Some(Some(ambiguity)) => {
+                    d_grid.highlight(grid, config, &ambiguity.cells, RenderVariant::SameValue);
+                    let value_map = config.value_map();
+                    let d_value = |solution: &Grid<N>, coordinate: Coordinate| {
+                        solution
+                            .get_cell(coordinate)
+                            .value
+                            .and_then(|value| value_map.get(&value).cloned())
+                            .unwrap_or_default()
+                    };
+                    let [first, second] = &ambiguity.solutions;
+                    let mut differences: Vec<String> = ambiguity
+                        .cells
+                        .iter()
+                        .take(REVEALED_CELLS)
+                        .map(|coordinate| {
+                            format!(
+                                "{} {}|{}",
+                                coordinate,
+                                d_value(first, *coordinate),
+                                d_value(second, *coordinate)
+                            )
+                        })
+                        .collect();
+                    if ambiguity.cells.len() > REVEALED_CELLS {
+                        differences.push(String::from("..."));
+                    }
+                    d_grid.render_status(&format!(
+                        "Solutions differ in {} highlighted cells: {}",
+                        ambiguity.cells.len(),
+                        differences.join(", ")
+                    ));
+                }
+                Some(None) => d_grid.render_status("Grid does not have several solutions"),
+                None => d_grid.render_status(UNKNOWN),
+            }
+        } else if let Some(value) = key_value::<N>(&event) {
+            let old_value = grid.get_cell(d_grid.active).value;
+            grid.set_cell_value(d_grid.active, Some(value)).unwrap();
+            d_grid.set_value(grid, config, d_grid.active, old_value, Some(value));
+            render_validity(&d_grid, grid);
+            saved = false;
+            Recovery::save(Mode::Make, save_path, &game);
+        }
+    }
+
+    drop(session);
+    Recovery::<N>::clear();
+    Ok(())
+}
+
+/// show conflicts and whether the current values have no, one or several solutions,
+/// unknown if counting takes too long
+fn render_validity<const N: usize>(d_grid: &DGrid<N>, grid: &Grid<N>) {
+    let conflicts = match conflicts(grid).len() {
+        0 => String::from("No conflicts"),
+        1 => String::from("1 conflict"),
+        count => format!("{} conflicts", count),
+    };
+    let solutions = match Backtracking::uniqueness_within(grid, VALIDITY_BUDGET) {
+        Some(Uniqueness::None) => "no solution",
+        Some(Uniqueness::Unique) => "unique solution",
+        Some(Uniqueness::Multiple) => "several solutions",
+        None => "solutions unknown",
+    };
+    d_grid.render_indicator(&format!("{}, {}", conflicts, solutions));
+}
+
+/// mark every filled cell as a given, returns the number of new givens
+fn lock_givens<const N: usize>(grid: &mut Grid<N>) -> usize {
+    let coordinates: Vec<Coordinate> = grid
+        .rows
+        .iter()
+        .flat_map(|row| row.cells.iter())
+        .filter(|cell| cell.value.is_some() && cell.editable)
+        .map(|cell| cell.coordinate)
+        .collect();
+    for coordinate in &coordinates {
+        grid.set_cell_editable(*coordinate, false).unwrap();
+    }
+    coordinates.len()
+}
+
+/// show `question` in the status line until one of the `answers` keys is pressed
+pub(super) fn ask<const N: usize>(d_grid: &DGrid<N>, question: &str, answers: &str) -> char {
+    d_grid.render_status(question);
+    loop {
+        if let Event::Key(KeyEvent {
+            code: KeyCode::Char(c),
+            ..
+        }) = read().unwrap()
+        {
+            if answers.contains(c) {
+                return c;
+            }
+        }
+    }
+}
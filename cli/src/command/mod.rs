@@ -0,0 +1,43 @@
+mod batch;
+mod check;
+mod convert;
+mod dedupe;
+mod export;
+mod generate;
+mod hint;
+mod make;
+mod play;
+mod solve;
+mod stats;
+
+pub use batch::{grade_batch_file, solve_batch_file};
+pub use check::{check, CHECK_FORMATS, EXIT_INVALID};
+pub use convert::{convert, CONVERT_FORMATS};
+pub use dedupe::dedupe;
+pub use export::{export, EXPORT_FORMATS};
+pub use generate::{collection_format, generate, parse_range, COLLECTION_FORMATS, GENERATE_SIZES};
+pub use make::make;
+pub use play::play;
+pub use solve::{solve, EXIT_MULTIPLE_SOLUTIONS, EXIT_UNSOLVABLE, SOLVE_FORMATS};
+pub use stats::{stats, STATS_FORMATS};
+
+use std::io::{self, stdout, ErrorKind, Write};
+
+/// Write `content` to stdout, a reader that stops early (e.g. `head`) is not an error
+pub fn print_output(content: &str) -> Result<(), String> {
+    let mut stdout = stdout().lock();
+    let written = stdout
+        .write_all(content.as_bytes())
+        .and_then(|()| stdout.flush());
+    closed(written).map(|_| ())
+}
+
+/// whether writing output failed because its reader stopped early, the command then stops
+/// quietly, other errors are returned
+fn closed(written: io::Result<()>) -> Result<bool, String> {
+    match written {
+        Ok(()) => Ok(false),
+        Err(error) if error.kind() == ErrorKind::BrokenPipe => Ok(true),
+        Err(error) => Err(error.to_string()),
+    }
+}
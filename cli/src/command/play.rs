@@ -0,0 +1,436 @@
+use super::hint::Hint;
+use super::make::ask;
+use crate::config::{key_binding::KeyBinding, Config};
+use crate::display::{render_coordinate_guide, render_screen, DGrid, Session};
+use crate::enums::{MistakeCheck, Navigation};
+use crate::game::{format_time, Clock, Game, Mode, Recovery, Stats, AUTOSAVE_INTERVAL};
+use crossterm::event::{poll, read, Event, KeyCode, KeyEvent, KeyModifiers};
+use std::time::{Duration, Instant};
+use sudokube::{
+    analysis::{conflicts, givens},
+    model::{Coordinate, Grid},
+    solver::{Backtracking, Solver},
+};
+
+/// interval at which the clock is redrawn
+const TICK: Duration = Duration::from_secs(1);
+
+///
+/// Play `game` in the terminal until it is solved or the player quits.
+/// The game is saved to `save_path` on the save key, on quit if confirmed,
+/// and with its final time once solved.
+/// Returns whether the puzzle was solved.
+///
+pub fn play<const N: usize>(mut game: Game<N>, save_path: Option<&str>, config: &Config) -> bool {
+    lock_givens(&mut game.grid);
+    let mut config = game.config(config);
+    let config = &mut config;
+    let mut d_grid: DGrid<N> = DGrid::new(&game.grid, Coordinate(1, 2));
+    let mut clock = Clock::new(game.elapsed);
+    let mut autosaved = Instant::now();
+    let mut hint: Option<Hint> = None;
+    let mut saved = true;
+    let mistake_check = config.mistake_check();
+    let solution = unique_solution(&game.grid);
+    if game.id.is_none() && !game.solved {
+        Stats::start(&mut game);
+    }
+
+    let session = Session::start();
+    d_grid.active = Coordinate(N as u8 / 2, N as u8 / 2);
+    render_board(&mut d_grid, &game.grid, config);
+    // flagged before the game was saved, already counted
+    d_grid.mark_incorrect(&game.grid, config, &game.incorrect);
+    if let (MistakeCheck::Immediate, Some(solution)) = (mistake_check, &solution) {
+        // entered while mistakes were not checked immediately
+        let wrong = mistakes(&game.grid, solution);
+        if flag_mistakes(&mut game, &mut d_grid, config, &wrong) > 0 {
+            saved = false;
+        }
+    }
+    d_grid.rerender(&game.grid, config);
+    if !game.solved {
+        clock.start();
+    }
+    render_progress(&d_grid, &game, &clock);
+
+    let key_binding = config.key_binding();
+    let navigation_key_events = key_binding.navigation();
+
+    // a game solved before only shows its result
+    let solved = game.solved
+        || loop {
+            if !poll(TICK).unwrap() {
+                if autosaved.elapsed() >= AUTOSAVE_INTERVAL {
+                    game.elapsed = clock.elapsed();
+                    Recovery::save(Mode::Play, save_path, &game);
+                    autosaved = Instant::now();
+                }
+                if clock.running() {
+                    render_progress(&d_grid, &game, &clock);
+                }
+                continue;
+            }
+            let event = read().unwrap();
+            let moves = game.history.len();
+
+            if event == navigation_key_events.left().crossterm() {
+                d_grid.navigate(&game.grid, config, Navigation::Col(-1));
+            } else if event == navigation_key_events.up().crossterm() {
+                d_grid.navigate(&game.grid, config, Navigation::Row(-1));
+            } else if event == navigation_key_events.right().crossterm() {
+                d_grid.navigate(&game.grid, config, Navigation::Col(1));
+            } else if event == navigation_key_events.down().crossterm() {
+                d_grid.navigate(&game.grid, config, Navigation::Row(1));
+            } else if event == navigation_key_events.next_group().crossterm() {
+                d_grid.navigate(&game.grid, config, Navigation::Group(1));
+            } else if event == navigation_key_events.previous_group().crossterm() {
+                d_grid.navigate(&game.grid, config, Navigation::Group(-1));
+            } else if event == key_binding.quit().crossterm() {
+                if confirm_quit(&mut game, &d_grid, save_path, saved, &clock) {
+                    break false;
+                }
+                continue;
+            } else if event == key_binding.suspend().crossterm() {
+                clock.pause();
+                session.suspend();
+                clock.start();
+                render_board(&mut d_grid, &game.grid, config);
+            } else if event == key_binding.pause().crossterm() {
+                clock.pause();
+                let quit = pause(&session, &key_binding, &clock);
+                render_board(&mut d_grid, &game.grid, config);
+                d_grid.render_status("");
+                if quit && confirm_quit(&mut game, &d_grid, save_path, saved, &clock) {
+                    break false;
+                }
+                clock.start();
+                render_progress(&d_grid, &game, &clock);
+                continue;
+            } else if event == key_binding.toggle_context_highlight().crossterm() {
+                config.toggle_context_highlight();
+                d_grid.rerender(&game.grid, config);
+            } else if event == key_binding.delete().crossterm() {
+                enter(&mut game, &mut d_grid, config, None);
+            } else if event == key_binding.undo().crossterm() {
+                if let Some(entry) = game.undo() {
+                    d_grid.set_value(&game.grid, config, entry.coordinate, entry.to, entry.from);
+                    d_grid.navigate_to(&game.grid, config, entry.coordinate);
+                }
+            } else if event == key_binding.hint().crossterm() {
+                if hint.is_none() {
+                    hint = Hint::find(&game);
+                    match hint {
+                        Some(_) => {
+                            game.hints += 1;
+                            saved = false;
+                        }
+                        None => {
+                            d_grid.render_status("No hint found, some entries may be wrong");
+                            continue;
+                        }
+                    }
+                }
+                if let Some(current) = &mut hint {
+                    let applied = current.reveal(&mut game, &mut d_grid, config);
+                    // the hint count, and once applied the entries, changed
+                    render_progress(&d_grid, &game, &clock);
+                    if applied {
+                        hint = None;
+                        game.elapsed = clock.elapsed();
+                        Recovery::save(Mode::Play, save_path, &game);
+                        if is_solved(&game.grid) {
+                            break true;
+                        }
+                    }
+                }
+                continue;
+            } else if event == key_binding.check_mistakes().crossterm() {
+                let message = match (mistake_check, &solution) {
+                    (MistakeCheck::Off, _) => String::from("Mistake checking is off"),
+                    (_, None) => String::from("No unique solution to check against"),
+                    (_, Some(solution)) => {
+                        let wrong = mistakes(&game.grid, solution);
+                        if flag_mistakes(&mut game, &mut d_grid, config, &wrong) > 0 {
+                            saved = false;
+                        }
+                        d_grid.rerender(&game.grid, config);
+                        match wrong.len() {
+                            0 => String::from("No wrong entries"),
+                            1 => String::from("1 wrong entry"),
+                            count => format!("{} wrong entries", count),
+                        }
+                    }
+                };
+                d_grid.render_status(&message);
+                continue;
+            } else if event == key_binding.save().crossterm() {
+                game.elapsed = clock.elapsed();
+                let message = match save_path {
+                    Some(path) => match game.write(path) {
+                        Ok(()) => {
+                            saved = true;
+                            format!("Saved to {}", path)
+                        }
+                        Err(error) => error,
+                    },
+                    None => String::from("No file to save to, start with --output"),
+                };
+                d_grid.render_status(&message);
+                continue;
+            } else if let Some(value) = key_value::<N>(&event) {
+                if enter(&mut game, &mut d_grid, config, Some(value)) {
+                    if is_solved(&game.grid) {
+                        break true;
+                    }
+                    if let (MistakeCheck::Immediate, Some(solution)) = (mistake_check, &solution) {
+                        if solution.get_cell(d_grid.active).value != Some(value) {
+                            let active = d_grid.active;
+                            flag_mistakes(&mut game, &mut d_grid, config, &[active]);
+                            d_grid.rerender(&game.grid, config);
+                        }
+                    }
+                }
+            } else {
+                continue;
+            }
+
+            if game.history.len() != moves {
+                hint = None;
+                saved = false;
+                game.elapsed = clock.elapsed();
+                Recovery::save(Mode::Play, save_path, &game);
+            }
+            d_grid.render_status("");
+            render_progress(&d_grid, &game, &clock);
+        };
+
+    if solved {
+        let mut notes = String::new();
+        if !game.solved {
+            clock.pause();
+            game.elapsed = clock.elapsed();
+            game.solved = true;
+            if Stats::complete(&game) {
+                notes.push_str("New best time!\n\n");
+            }
+            if let Some(Err(error)) = save_path.map(|path| game.write(path)) {
+                notes.push_str(&format!("{}\n\n", error));
+            }
+        }
+        render_screen(
+            Coordinate(1, 2),
+            &format!(
+                "Puzzle solved in {}!\n\n{}\n\n{} moves, {} hints, {} mistakes\n\n\
+                {}Press any key to exit",
+                format_time(game.elapsed),
+                d_grid.text(&game.grid, config),
+                game.history.len(),
+                game.hints,
+                game.mistakes,
+                notes
+            ),
+        );
+        while !matches!(read().unwrap(), Event::Key(_)) {}
+    }
+
+    drop(session);
+    Recovery::<N>::clear();
+    solved
+}
+
+/// render the coordinate guide and the whole grid, keeping the active cell
+pub(super) fn render_board<const N: usize>(d_grid: &mut DGrid<N>, grid: &Grid<N>, config: &Config) {
+    render_coordinate_guide(Coordinate(0, 0), N as u8);
+    d_grid.render(grid, config);
+    d_grid.navigate_to(grid, config, d_grid.active);
+}
+
+///
+/// Whether to quit: like in `make`, unsaved changes are written to `save_path`
+/// or dropped only once confirmed.
+///
+fn confirm_quit<const N: usize>(
+    game: &mut Game<N>,
+    d_grid: &DGrid<N>,
+    save_path: Option<&str>,
+    saved: bool,
+    clock: &Clock,
+) -> bool {
+    if saved {
+        return true;
+    }
+    let quit = match save_path {
+        Some(path) => match ask(d_grid, &format!("Save to {}? (y/n/c)", path), "ync") {
+            'y' => {
+                game.elapsed = clock.elapsed();
+                match game.write(path) {
+                    Ok(()) => true,
+                    Err(error) => {
+                        d_grid.render_status(&error);
+                        return false;
+                    }
+                }
+            }
+            'n' => true,
+            _ => false,
+        },
+        None => ask(d_grid, "Quit without saving? (y/n)", "yn") == 'y',
+    };
+    if !quit {
+        d_grid.render_status("");
+    }
+    quit
+}
+
+/// show the time played and the progress in the line below the status
+fn render_progress<const N: usize>(d_grid: &DGrid<N>, game: &Game<N>, clock: &Clock) {
+    d_grid.render_indicator(&format!(
+        "Time {}, {}",
+        format_time(clock.elapsed()),
+        progress(game)
+    ));
+}
+
+///
+/// Hide the board until the pause key is pressed again, so the paused time cannot be used.
+/// Returns whether the player quit instead.
+///
+fn pause(session: &Session, key_binding: &KeyBinding, clock: &Clock) -> bool {
+    let text = format!(
+        "Paused at {}\n\nPress the pause key to resume",
+        format_time(clock.elapsed())
+    );
+    render_screen(Coordinate(1, 2), &text);
+    loop {
+        let event = read().unwrap();
+        if event == key_binding.pause().crossterm() {
+            return false;
+        } else if event == key_binding.quit().crossterm() {
+            return true;
+        } else if event == key_binding.suspend().crossterm() {
+            session.suspend();
+            render_screen(Coordinate(1, 2), &text);
+        }
+    }
+}
+
+///
+/// Givens of a puzzle made in `make` mode are not marked yet:
+/// if no cell is locked, lock every filled cell.
+///
+fn lock_givens<const N: usize>(grid: &mut Grid<N>) {
+    let cells: Vec<(Coordinate, bool)> = grid
+        .rows
+        .iter()
+        .flat_map(|row| row.cells.iter())
+        .map(|cell| (cell.coordinate, cell.value.is_some()))
+        .collect();
+    if cells
+        .iter()
+        .any(|(coordinate, _)| !grid.get_cell(*coordinate).editable)
+    {
+        return;
+    }
+    for (coordinate, filled) in cells {
+        if filled {
+            grid.set_cell_editable(coordinate, false).unwrap();
+        }
+    }
+}
+
+/// set the active cell to `value` unless it is a given, returns whether anything changed
+fn enter<const N: usize>(
+    game: &mut Game<N>,
+    d_grid: &mut DGrid<N>,
+    config: &Config,
+    value: Option<u8>,
+) -> bool {
+    match game.enter(d_grid.active, value) {
+        Some(entry) => {
+            d_grid.set_value(&game.grid, config, entry.coordinate, entry.from, entry.to);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 0-based value of a digit key, if within the grid
+pub(super) fn key_value<const N: usize>(event: &Event) -> Option<u8> {
+    match event {
+        Event::Key(KeyEvent {
+            modifiers: KeyModifiers::NONE,
+            code: KeyCode::Char(c),
+        }) => match c.to_digit(10) {
+            Some(digit) if digit >= 1 && digit as usize <= N => Some(digit as u8 - 1),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// solution of the givens, if there is exactly one
+fn unique_solution<const N: usize>(grid: &Grid<N>) -> Option<Grid<N>> {
+    let mut solutions = Backtracking.solutions(&givens(grid), 2);
+    if solutions.len() == 1 {
+        solutions.pop()
+    } else {
+        None
+    }
+}
+
+/// flag `wrong` entries as incorrect, returns how many were new and counted as mistakes
+fn flag_mistakes<const N: usize>(
+    game: &mut Game<N>,
+    d_grid: &mut DGrid<N>,
+    config: &Config,
+    wrong: &[Coordinate],
+) -> usize {
+    let marked = d_grid.mark_incorrect(&game.grid, config, wrong);
+    game.mistakes += marked;
+    for coordinate in wrong {
+        if !game.incorrect.contains(coordinate) {
+            game.incorrect.push(*coordinate);
+        }
+    }
+    marked
+}
+
+/// entries that differ from `solution`
+fn mistakes<const N: usize>(grid: &Grid<N>, solution: &Grid<N>) -> Vec<Coordinate> {
+    grid.rows
+        .iter()
+        .flat_map(|row| row.cells.iter())
+        .filter(|cell| {
+            cell.editable
+                && cell.value.is_some()
+                && solution.get_cell(cell.coordinate).value != cell.value
+        })
+        .map(|cell| cell.coordinate)
+        .collect()
+}
+
+fn is_solved<const N: usize>(grid: &Grid<N>) -> bool {
+    let complete = grid
+        .rows
+        .iter()
+        .all(|row| row.cells.iter().all(|cell| cell.value.is_some()));
+    complete && conflicts(grid).is_empty()
+}
+
+fn progress<const N: usize>(game: &Game<N>) -> String {
+    let filled = game
+        .grid
+        .rows
+        .iter()
+        .map(|row| row.cells.iter().filter(|cell| cell.value.is_some()).count())
+        .sum::<usize>();
+    format!(
+        "{}/{} filled, {} moves, {} hints, {} mistakes",
+        filled,
+        N * N,
+        game.history.len(),
+        game.hints,
+        game.mistakes
+    )
+}
@@ -0,0 +1,49 @@
+use super::print_output;
+use crate::config::Config;
+use crate::display::DGrid;
+use sudokube::{
+    batch::Solved,
+    format::format_line,
+    model::{Coordinate, Grid},
+    solver::{Backtracking, Uniqueness},
+};
+
+pub const SOLVE_FORMATS: [&str; 3] = ["pretty", "line", "json"];
+
+/// exit code when the grid has no solution
+pub const EXIT_UNSOLVABLE: i32 = 2;
+/// exit code when the grid has more than one solution
+pub const EXIT_MULTIPLE_SOLUTIONS: i32 = 3;
+
+///
+/// Print the solution of `grid` in `format` if it is unique.
+/// Returns the uniqueness of the solution so the caller can pick an exit code.
+///
+pub fn solve<const N: usize>(
+    grid: &Grid<N>,
+    format: &str,
+    config: &Config,
+) -> Result<Uniqueness, String> {
+    let solved = Solved::new(&Backtracking, grid);
+    let solution = match (solved.uniqueness, &solved.solution) {
+        (Uniqueness::Unique, Some(solution)) => solution,
+        (Uniqueness::Multiple, _) => {
+            eprintln!("Grid has multiple solutions");
+            return Ok(Uniqueness::Multiple);
+        }
+        _ => {
+            eprintln!("Grid has no solution");
+            return Ok(Uniqueness::None);
+        }
+    };
+
+    let output = match format {
+        "pretty" => DGrid::new(solution, Coordinate(0, 0)).text(solution, config),
+        "line" => format_line(solution).map_err(|error| error.to_string())?,
+        "json" => solution.to_json(),
+        _ => return Err(format!("Unsupported output format {}", format)),
+    };
+    print_output(&format!("{}\n", output))?;
+
+    Ok(Uniqueness::Unique)
+}
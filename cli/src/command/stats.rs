@@ -0,0 +1,167 @@
+use super::print_output;
+use crate::game::{format_time, Record, Stats, Summary, DAY};
+use serde_json::json;
+use std::time::Duration;
+
+pub const STATS_FORMATS: [&str; 2] = ["text", "json"];
+
+///
+/// Print the statistics of the games played in `format`: totals, streaks, a summary by size
+/// and difficulty and the `history` most recent games, all of them if 0.
+///
+pub fn stats(format: &str, history: usize) -> Result<(), String> {
+    let stats = Stats::load()?;
+    let recent: Vec<&Record> = match history {
+        0 => stats.games.iter().rev().collect(),
+        count => stats.games.iter().rev().take(count).collect(),
+    };
+    let summaries = stats.summaries();
+
+    let output = match format {
+        "json" => format!(
+            "{}\n",
+            json!({
+                "started": stats.games.len(),
+                "completed": summaries.iter().map(|summary| summary.completed).sum::<usize>(),
+                "hints": summaries.iter().map(|summary| summary.hints).sum::<usize>(),
+                "mistakes": summaries.iter().map(|summary| summary.mistakes).sum::<usize>(),
+                "streak": stats.streak(),
+                "summaries": summaries,
+                "history": recent,
+            })
+        ),
+        "text" => stats_text(&stats, &summaries, &recent),
+        _ => return Err(format!("Unsupported output format {}", format)),
+    };
+    print_output(&output)
+}
+
+fn stats_text(stats: &Stats, summaries: &[Summary], recent: &[&Record]) -> String {
+    if stats.games.is_empty() {
+        return String::from("No games played yet\n");
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let completed: usize = summaries.iter().map(|summary| summary.completed).sum();
+    lines.push(format!(
+        "Games: {} started, {} completed",
+        stats.games.len(),
+        completed
+    ));
+    let streak = stats.streak();
+    lines.push(format!(
+        "Streak: {}, longest {}",
+        days(streak.current),
+        days(streak.longest)
+    ));
+    lines.push(format!(
+        "Hints: {}, mistakes: {}",
+        summaries.iter().map(|summary| summary.hints).sum::<usize>(),
+        summaries
+            .iter()
+            .map(|summary| summary.mistakes)
+            .sum::<usize>()
+    ));
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{:<6}{:<12}{:>8}{:>11}{:>9}{:>9}{:>7}{:>10}",
+        "Size", "Difficulty", "Started", "Completed", "Best", "Average", "Hints", "Mistakes"
+    ));
+    for summary in summaries {
+        lines.push(format!(
+            "{:<6}{:<12}{:>8}{:>11}{:>9}{:>9}{:>7}{:>10}",
+            size(summary.size),
+            summary.difficulty.to_string(),
+            summary.started,
+            summary.completed,
+            time(summary.best),
+            time(summary.average),
+            summary.hints,
+            summary.mistakes
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{:<12}{:<6}{:<12}{:>9}{:>7}{:>10}",
+        "Date", "Size", "Difficulty", "Time", "Hints", "Mistakes"
+    ));
+    for record in recent {
+        lines.push(format!(
+            "{:<12}{:<6}{:<12}{:>9}{:>7}{:>10}",
+            date(record.started),
+            size(record.size),
+            record.difficulty.to_string(),
+            time(record.time),
+            record.hints,
+            record.mistakes
+        ));
+    }
+
+    format!("{}\n", lines.join("\n"))
+}
+
+fn days(count: usize) -> String {
+    match count {
+        1 => String::from("1 day"),
+        count => format!("{} days", count),
+    }
+}
+
+fn size(size: usize) -> String {
+    format!("{}x{}", size, size)
+}
+
+/// seconds played, `-` for games not completed
+fn time(seconds: Option<u64>) -> String {
+    match seconds {
+        Some(seconds) => format_time(Duration::from_secs(seconds)),
+        None => String::from("-"),
+    }
+}
+
+/// UTC date of a unix time as `yyyy-mm-dd`
+fn date(timestamp: u64) -> String {
+    // days to civil date, from Howard Hinnant's date algorithms
+    let z = timestamp / DAY + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_of_unix_times() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(DAY - 1), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_709_251_199), "2024-02-29");
+        assert_eq!(date(1_735_689_599), "2024-12-31");
+        assert_eq!(date(1_735_689_600), "2025-01-01");
+        assert_eq!(date(4_107_542_400), "2100-03-01");
+    }
+
+    #[test]
+    fn plural_days_sizes_and_times() {
+        assert_eq!(days(1), "1 day");
+        assert_eq!(days(0), "0 days");
+        assert_eq!(size(16), "16x16");
+        assert_eq!(time(None), "-");
+        assert_eq!(time(Some(3725)), "1:02:05");
+    }
+}
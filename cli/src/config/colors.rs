@@ -59,6 +59,8 @@ pub struct ColorsConfig {
     #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
     same_value: Option<DisplayColor>,
     #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    incorrect: Option<DisplayColor>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
     default: Option<DisplayColor>,
 }
 impl Default for ColorsConfig {
@@ -68,6 +70,7 @@ impl Default for ColorsConfig {
             error: Some(Self::default_error()),
             directional_relative: Some(Self::default_directional_relative()),
             same_value: Some(Self::default_same_value()),
+            incorrect: Some(Self::default_incorrect()),
             default: Some(Self::default_default()),
         }
     }
@@ -79,6 +82,7 @@ impl ColorsConfig {
             error: None,
             directional_relative: None,
             same_value: None,
+            incorrect: None,
             default: None,
         }
     }
@@ -91,6 +95,7 @@ impl ColorsConfig {
                 .directional_relative
                 .unwrap_or(Self::default_directional_relative()),
             RenderVariant::SameValue => self.same_value.unwrap_or(Self::default_same_value()),
+            RenderVariant::Incorrect => self.incorrect.unwrap_or(Self::default_incorrect()),
             RenderVariant::Default => self.default.unwrap_or(Self::default_default()),
         }
     }
@@ -125,6 +130,13 @@ impl ColorsConfig {
         }
     }
 
+    fn default_incorrect() -> DisplayColor {
+        DisplayColor {
+            bg: None,
+            color: Some(Rgb(255, 135, 0)),
+        }
+    }
+
     fn default_default() -> DisplayColor {
         DisplayColor::default()
     }
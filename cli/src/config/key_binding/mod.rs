@@ -19,6 +19,24 @@ pub struct KeyBinding {
     #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
     delete: Option<KeyDefinition>,
     #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    diagnose: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    reveal_solutions: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    undo: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    hint: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    check_mistakes: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    save: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    lock_givens: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    suspend: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    pause: Option<KeyDefinition>,
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
     quit: Option<KeyDefinition>,
 }
 
@@ -28,6 +46,15 @@ impl Default for KeyBinding {
             navigation: Some(NavigationKeyBinding::default()),
             toggle_context_highlight: Some(KeyBinding::default_toggle_context_highlight()),
             delete: Some(KeyBinding::default_delete()),
+            diagnose: Some(KeyBinding::default_diagnose()),
+            reveal_solutions: Some(KeyBinding::default_reveal_solutions()),
+            undo: Some(KeyBinding::default_undo()),
+            hint: Some(KeyBinding::default_hint()),
+            check_mistakes: Some(KeyBinding::default_check_mistakes()),
+            save: Some(KeyBinding::default_save()),
+            lock_givens: Some(KeyBinding::default_lock_givens()),
+            suspend: Some(KeyBinding::default_suspend()),
+            pause: Some(KeyBinding::default_pause()),
             quit: Some(KeyBinding::default_quit()),
         }
     }
@@ -39,6 +66,15 @@ impl KeyBinding {
             navigation: None,
             toggle_context_highlight: None,
             delete: None,
+            diagnose: None,
+            reveal_solutions: None,
+            undo: None,
+            hint: None,
+            check_mistakes: None,
+            save: None,
+            lock_givens: None,
+            suspend: None,
+            pause: None,
             quit: None,
         }
     }
@@ -51,6 +87,45 @@ impl KeyBinding {
         self.delete.unwrap_or(KeyBinding::default_delete())
     }
 
+    pub fn diagnose(&self) -> KeyDefinition {
+        self.diagnose.unwrap_or(KeyBinding::default_diagnose())
+    }
+
+    pub fn reveal_solutions(&self) -> KeyDefinition {
+        self.reveal_solutions
+            .unwrap_or(KeyBinding::default_reveal_solutions())
+    }
+
+    pub fn undo(&self) -> KeyDefinition {
+        self.undo.unwrap_or(KeyBinding::default_undo())
+    }
+
+    pub fn hint(&self) -> KeyDefinition {
+        self.hint.unwrap_or(KeyBinding::default_hint())
+    }
+
+    pub fn check_mistakes(&self) -> KeyDefinition {
+        self.check_mistakes
+            .unwrap_or(KeyBinding::default_check_mistakes())
+    }
+
+    pub fn save(&self) -> KeyDefinition {
+        self.save.unwrap_or(KeyBinding::default_save())
+    }
+
+    pub fn lock_givens(&self) -> KeyDefinition {
+        self.lock_givens
+            .unwrap_or(KeyBinding::default_lock_givens())
+    }
+
+    pub fn suspend(&self) -> KeyDefinition {
+        self.suspend.unwrap_or(KeyBinding::default_suspend())
+    }
+
+    pub fn pause(&self) -> KeyDefinition {
+        self.pause.unwrap_or(KeyBinding::default_pause())
+    }
+
     pub fn quit(&self) -> KeyDefinition {
         self.quit.unwrap_or(KeyBinding::default_quit())
     }
@@ -75,6 +150,69 @@ impl KeyBinding {
         }
     }
 
+    fn default_diagnose() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('D')),
+            modifier: Some(KeyModifier::Shift),
+        }
+    }
+
+    fn default_reveal_solutions() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('R')),
+            modifier: Some(KeyModifier::Shift),
+        }
+    }
+
+    fn default_undo() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('u')),
+            modifier: None,
+        }
+    }
+
+    fn default_hint() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('?')),
+            modifier: None,
+        }
+    }
+
+    fn default_check_mistakes() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('C')),
+            modifier: Some(KeyModifier::Shift),
+        }
+    }
+
+    fn default_save() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('s')),
+            modifier: Some(KeyModifier::Control),
+        }
+    }
+
+    fn default_lock_givens() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('L')),
+            modifier: Some(KeyModifier::Shift),
+        }
+    }
+
+    fn default_suspend() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('z')),
+            modifier: Some(KeyModifier::Control),
+        }
+    }
+
+    fn default_pause() -> KeyDefinition {
+        KeyDefinition {
+            code: Some(KeyCode::Char('p')),
+            modifier: None,
+        }
+    }
+
     fn default_quit() -> KeyDefinition {
         KeyDefinition {
             code: Some(KeyCode::Char('q')),
@@ -3,6 +3,7 @@ pub mod key_binding;
 
 use crate::config::colors::ColorsConfig;
 use crate::config::key_binding::KeyBinding;
+use crate::enums::MistakeCheck;
 use dirs::home_dir;
 use merge::Merge;
 use serde::{Deserialize, Serialize};
@@ -12,7 +13,7 @@ use std::collections::HashMap;
 use std::fs;
 
 #[serde_as]
-#[derive(Merge, Debug, Serialize, Deserialize)]
+#[derive(Merge, Debug, Clone, Serialize, Deserialize)]
 #[serde(default = "Config::blank")]
 pub struct Config {
     #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
@@ -27,6 +28,9 @@ pub struct Config {
 
     #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
     key_binding: Option<KeyBinding>,
+
+    #[merge(strategy = crate::lib::merge::strategy::option::overwrite)]
+    mistake_check: Option<MistakeCheck>,
 }
 
 impl Default for Config {
@@ -36,6 +40,7 @@ impl Default for Config {
             colors: Some(ColorsConfig::default()),
             value_map: Some(Self::default_value_map()),
             key_binding: Some(KeyBinding::default()),
+            mistake_check: Some(Self::default_mistake_check()),
         }
     }
 }
@@ -48,6 +53,7 @@ impl Config {
             colors: None,
             value_map: None,
             key_binding: None,
+            mistake_check: None,
         }
     }
 
@@ -65,9 +71,14 @@ impl Config {
         }
     }
 
+    /// write to a file of its own first, so processes reading `path` meanwhile see a whole file
     pub fn write(&self, path: &str) -> Result<(), std::io::Error> {
-        fs::write(path, self.to_yaml())?;
-        Ok(())
+        let temporary = format!("{}.{}.tmp", path, std::process::id());
+        fs::write(&temporary, self.to_yaml())?;
+        fs::rename(&temporary, path).map_err(|error| {
+            let _ = fs::remove_file(&temporary);
+            error
+        })
     }
 
     pub fn toggle_context_highlight(&mut self) {
@@ -96,11 +107,15 @@ impl Config {
         self.key_binding.unwrap_or(KeyBinding::default())
     }
 
+    pub fn mistake_check(&self) -> MistakeCheck {
+        self.mistake_check.unwrap_or(Self::default_mistake_check())
+    }
+
     pub fn from_yaml(yaml: &str) -> Self {
         match from_str(yaml) {
             Ok(config) => config,
             Err(error) => {
-                println!("Error parsing config file: {}", error);
+                eprintln!("Error parsing config file: {}", error);
                 Config::blank()
             }
         }
@@ -110,7 +125,7 @@ impl Config {
         match to_string(self) {
             Ok(yaml) => yaml,
             Err(error) => {
-                println!("Error serializing config file: {}", error);
+                eprintln!("Error serializing config file: {}", error);
                 String::default()
             }
         }
@@ -136,4 +151,8 @@ impl Config {
     fn default_context_highlight() -> bool {
         true
     }
+
+    fn default_mistake_check() -> MistakeCheck {
+        MistakeCheck::OnDemand
+    }
 }
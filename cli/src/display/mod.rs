@@ -1,4 +1,7 @@
 mod box_drawing;
+mod session;
+
+pub use session::Session;
 
 use crate::config::{colors::DisplayColor, Config};
 use crate::enums::{Navigation, RenderVariant};
@@ -10,6 +13,7 @@ use crossterm::{
     cursor::{Hide, MoveTo, RestorePosition, SavePosition, Show},
     execute,
     style::{Color, ContentStyle, Print, ResetColor, StyledContent, Stylize},
+    terminal::{Clear, ClearType},
 };
 use std::{collections::HashSet, io::stdout};
 use sudokube::model::{Cell, CellRelation, Coordinate, Grid};
@@ -46,6 +50,7 @@ pub struct DGrid<const N: usize> {
     origin: Coordinate,
     pub active: Coordinate,
     cells: [[DCell; N]; N],
+    incorrect: HashSet<Coordinate>, // entries shown as incorrect until their value changes
 }
 
 // public
@@ -65,6 +70,7 @@ impl<const N: usize> DGrid<N> {
             origin,
             active: Coordinate(0, 0),
             cells: cells.try_into().unwrap(),
+            incorrect: HashSet::new(),
         }
     }
 
@@ -157,13 +163,104 @@ impl<const N: usize> DGrid<N> {
         old_value: Option<u8>,
         new_value: Option<u8>,
     ) -> () {
+        self.incorrect.remove(&coordinate);
         self.render_cell_value(grid, config, coordinate, RenderVariant::Default);
         self.rerender_same_value_cells(grid, config, coordinate, old_value, new_value);
     }
+
+    /// grid drawn with box drawing characters as plain text, without colors
+    pub fn text(&self, grid: &Grid<N>, config: &Config) -> String {
+        let mut canvas = vec![vec![' '; 4 * N + 1]; 2 * N + 1];
+        for x in 0..N {
+            for y in 0..N {
+                let coordinate = Coordinate(x as u8, y as u8);
+                let d_cell = self.d_cell(coordinate);
+                let mut chars = self.border_chars(coordinate);
+                let d_value = self.d_value(config, grid.get_cell(coordinate).value);
+                if let Some(c) = d_value.chars().next() {
+                    chars.push((d_cell.coordinates.center, c));
+                }
+                for (Coordinate(row, col), c) in chars {
+                    canvas[row as usize][col as usize] = c;
+                }
+            }
+        }
+
+        canvas
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// render cells with `variant` until they are rerendered
+    pub fn highlight(
+        &mut self,
+        grid: &Grid<N>,
+        config: &Config,
+        coordinates: &[Coordinate],
+        variant: RenderVariant,
+    ) {
+        for coordinate in coordinates {
+            self.render_cell_value(grid, config, *coordinate, variant);
+        }
+    }
+
+    /// show `value` highlighted in the cell at `coordinate` without entering it,
+    /// until the cell is rerendered
+    pub fn preview(&mut self, grid: &Grid<N>, config: &Config, coordinate: Coordinate, value: u8) {
+        self.render_value(
+            grid,
+            config,
+            coordinate,
+            Some(value),
+            RenderVariant::SameValue,
+        );
+    }
+
+    /// show entries at `coordinates` as incorrect, returns how many were not shown so far
+    pub fn mark_incorrect(
+        &mut self,
+        grid: &Grid<N>,
+        config: &Config,
+        coordinates: &[Coordinate],
+    ) -> usize {
+        let mut marked = 0;
+        for coordinate in coordinates {
+            if self.incorrect.insert(*coordinate) {
+                marked += 1;
+            }
+            self.render_cell_value(grid, config, *coordinate, RenderVariant::Default);
+        }
+        marked
+    }
+
+    /// replace the line of text below the grid
+    pub fn render_status(&self, message: &str) {
+        self.render_below(0, message);
+    }
+
+    /// replace the line of text below the status line
+    pub fn render_indicator(&self, message: &str) {
+        self.render_below(1, message);
+    }
 }
 
 // private
 impl<const N: usize> DGrid<N> {
+    fn render_below(&self, line: u16, message: &str) {
+        let Coordinate(x, y) = self.origin;
+        execute!(
+            stdout(),
+            SavePosition,
+            MoveTo(y as u16, x as u16 + 2 * N as u16 + 1 + line),
+            Clear(ClearType::UntilNewLine),
+            Print(message),
+            RestorePosition,
+        )
+        .unwrap();
+    }
+
     fn d_value(&self, config: &Config, value: Option<u8>) -> String {
         let mut text = String::from(" ");
         if let Some(num) = value {
@@ -252,9 +349,10 @@ impl<const N: usize> DGrid<N> {
         self.cells[row as usize][col as usize].style = d_style;
     }
 
-    fn render_cell(&mut self, grid: &Grid<N>, config: &Config, coordinate: Coordinate) -> () {
-        let cell = grid.get_cell(coordinate);
+    /// box drawing characters around a cell, positioned relative to the grid origin
+    fn border_chars(&self, coordinate: Coordinate) -> Vec<(Coordinate, char)> {
         let d_cell = self.d_cell(coordinate);
+        let mut chars: Vec<(Coordinate, char)> = Vec::new();
 
         let coordinates = &d_cell.coordinates;
 
@@ -285,7 +383,7 @@ impl<const N: usize> DGrid<N> {
             },
             CornerPosition::TopLeft,
         );
-        render_plain_at(coordinates.top_left + self.origin, &top_left.to_string());
+        chars.push((coordinates.top_left, top_left));
         //--------------BOTTOM_LEFT---------------
         let bottom_left = build_corner_char(
             CornerRelative {
@@ -296,10 +394,7 @@ impl<const N: usize> DGrid<N> {
             },
             CornerPosition::BottomLeft,
         );
-        render_plain_at(
-            coordinates.bottom_left + self.origin,
-            &bottom_left.to_string(),
-        );
+        chars.push((coordinates.bottom_left, bottom_left));
         //--------------TOP_RIGHT---------------
         let top_right = build_corner_char(
             CornerRelative {
@@ -310,7 +405,7 @@ impl<const N: usize> DGrid<N> {
             },
             CornerPosition::TopRight,
         );
-        render_plain_at(coordinates.top_right + self.origin, &top_right.to_string());
+        chars.push((coordinates.top_right, top_right));
         //--------------BOTTOM_RIGHT---------------
         let bottom_right = build_corner_char(
             CornerRelative {
@@ -321,10 +416,7 @@ impl<const N: usize> DGrid<N> {
             },
             CornerPosition::BottomRight,
         );
-        render_plain_at(
-            coordinates.bottom_right + self.origin,
-            &bottom_right.to_string(),
-        );
+        chars.push((coordinates.bottom_right, bottom_right));
         //--------------LEFT_MIDDLE---------------
         let left_middle = build_middle_char(
             MiddleRelative {
@@ -333,10 +425,7 @@ impl<const N: usize> DGrid<N> {
             },
             MiddlePosition::Horizontal,
         );
-        render_plain_at(
-            coordinates.left_middle + self.origin,
-            &left_middle.to_string(),
-        );
+        chars.push((coordinates.left_middle, left_middle));
         //--------------RIGHT_MIDDLE---------------
         let right_middle = build_middle_char(
             MiddleRelative {
@@ -345,10 +434,7 @@ impl<const N: usize> DGrid<N> {
             },
             MiddlePosition::Horizontal,
         );
-        render_plain_at(
-            coordinates.right_middle + self.origin,
-            &right_middle.to_string(),
-        );
+        chars.push((coordinates.right_middle, right_middle));
         //--------------TOP_MIDDLE---------------
         let top_middle = build_middle_char(
             MiddleRelative {
@@ -357,9 +443,8 @@ impl<const N: usize> DGrid<N> {
             },
             MiddlePosition::Vertical,
         );
-        for i in 0..coordinates.top_middle.len() {
-            let coor = coordinates.top_middle[i];
-            render_plain_at(coor + self.origin, &top_middle.to_string());
+        for coor in coordinates.top_middle {
+            chars.push((coor, top_middle));
         }
         //--------------BOTTOM_MIDDLE---------------
         let bottom_middle = build_middle_char(
@@ -369,11 +454,19 @@ impl<const N: usize> DGrid<N> {
             },
             MiddlePosition::Vertical,
         );
-        for i in 0..coordinates.bottom_middle.len() {
-            let coor = coordinates.bottom_middle[i];
-            render_plain_at(coor + self.origin, &bottom_middle.to_string());
+        for coor in coordinates.bottom_middle {
+            chars.push((coor, bottom_middle));
         }
-        //----------------CENTER------------------
+
+        chars
+    }
+
+    fn render_cell(&mut self, grid: &Grid<N>, config: &Config, coordinate: Coordinate) -> () {
+        for (coor, c) in self.border_chars(coordinate) {
+            render_plain_at(coor + self.origin, &c.to_string());
+        }
+
+        let cell = grid.get_cell(coordinate);
         self.set_value(grid, config, cell.coordinate, None, cell.value);
     }
 
@@ -452,17 +545,29 @@ impl<const N: usize> DGrid<N> {
         grid: &Grid<N>,
         config: &Config,
         coordinate: Coordinate,
+        variant: RenderVariant,
+    ) {
+        let value = grid.get_cell(coordinate).value;
+        self.render_value(grid, config, coordinate, value, variant);
+    }
+
+    fn render_value(
+        &mut self,
+        grid: &Grid<N>,
+        config: &Config,
+        coordinate: Coordinate,
+        value: Option<u8>,
         mut variant: RenderVariant,
     ) {
         let cell = grid.get_cell(coordinate);
-        let d_value = self.d_value(config, cell.value);
+        let d_value = self.d_value(config, value);
         let d_cell = self.d_cell(coordinate);
 
         let value_coordinate = d_cell.coordinates.center;
         let mut d_style = d_cell.style;
 
-        if !cell.editable {
-            // if value is fixed, overwrite render variant
+        if !cell.editable && variant != RenderVariant::Error {
+            // if value is fixed, overwrite render variant (errors still shown)
             variant = RenderVariant::Fixed;
         }
 
@@ -479,6 +584,17 @@ impl<const N: usize> DGrid<N> {
             }
         }
 
+        if self.incorrect.contains(&coordinate) && variant != RenderVariant::Error {
+            // incorrect entries keep the background of their highlight
+            let incorrect = config.colors().get(RenderVariant::Incorrect);
+            if let Some(c) = incorrect.color {
+                d_style.color = Some(c);
+            }
+            if let Some(c) = incorrect.bg {
+                d_style.bg = Some(c);
+            }
+        }
+
         self.set_d_style(coordinate, d_style);
 
         if config.context_highlight() {
@@ -536,3 +652,11 @@ pub fn render_coordinate_guide(origin: Coordinate, size: u8) {
         render_plain_at(origin.shift(Some(2 * (i + 1)), None), &(i + 1).to_string());
     }
 }
+
+/// clear the screen and render `text` line by line from `origin`
+pub fn render_screen(origin: Coordinate, text: &str) {
+    execute!(stdout(), Clear(ClearType::All)).unwrap();
+    for (i, line) in text.lines().enumerate() {
+        render_plain_at(origin.shift(Some(i as u8), None), line);
+    }
+}
@@ -0,0 +1,75 @@
+use crossterm::{
+    cursor::{DisableBlinking, EnableBlinking, Show},
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+};
+use std::{io::stdout, panic, sync::Once};
+
+static PANIC_HOOK: Once = Once::new();
+
+///
+/// Raw mode on the alternate screen for the interactive modes.
+/// The terminal is restored when the session is dropped, and on panic.
+///
+pub struct Session;
+
+impl Session {
+    pub fn start() -> Session {
+        PANIC_HOOK.call_once(|| {
+            let default_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                restore();
+                default_hook(info);
+            }));
+        });
+        enter();
+        Session
+    }
+
+    ///
+    /// Stop the process like a shell job, as raw mode keeps the terminal from doing it on Ctrl-Z.
+    /// The terminal is restored while stopped and cleared on `fg`, ready for a full redraw.
+    ///
+    pub fn suspend(&self) {
+        restore();
+        #[cfg(unix)]
+        unsafe {
+            libc::raise(libc::SIGTSTP);
+        }
+        enter();
+        execute!(stdout(), Clear(ClearType::All)).unwrap();
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+fn enter() {
+    enable_raw_mode().unwrap();
+    execute!(
+        stdout(),
+        EnableMouseCapture,
+        EnterAlternateScreen,
+        DisableBlinking,
+    )
+    .unwrap();
+}
+
+/// best effort, as it also runs while panicking
+fn restore() {
+    let _ = execute!(
+        stdout(),
+        DisableMouseCapture,
+        LeaveAlternateScreen,
+        EnableBlinking,
+        Show,
+    );
+    let _ = disable_raw_mode();
+}
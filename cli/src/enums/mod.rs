@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug)]
 pub enum Navigation {
     Col(i8),
@@ -12,6 +14,7 @@ pub enum RenderVariant {
     Fixed, // cell not editable
     DirectionalRelative, // for cells in same col/row
     SameValue, // for cells with same value
+    Incorrect, // for entries that differ from the solution
 }
 
 impl std::fmt::Display for RenderVariant {
@@ -19,3 +22,12 @@ impl std::fmt::Display for RenderVariant {
         write!(f, "{:?}", self)
     }
 }
+
+/// when play checks entries against the solution
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Copy, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub enum MistakeCheck {
+    Immediate, // flag wrong digits as they are entered
+    OnDemand,  // flag wrong digits on the check_mistakes key
+    Off,
+}
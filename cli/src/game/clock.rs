@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+
+/// time spent on a game, counted only while running
+pub struct Clock {
+    elapsed: Duration, // time counted before the current run
+    started: Option<Instant>,
+}
+
+impl Clock {
+    /// a paused clock showing `elapsed`
+    pub fn new(elapsed: Duration) -> Clock {
+        Clock {
+            elapsed,
+            started: None,
+        }
+    }
+
+    pub fn start(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.elapsed = self.elapsed();
+        self.started = None;
+    }
+
+    pub fn running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match self.started {
+            Some(started) => self.elapsed + started.elapsed(),
+            None => self.elapsed,
+        }
+    }
+}
+
+/// `duration` as `m:ss`, or `h:mm:ss` from an hour
+pub fn format_time(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_only_while_running() {
+        let mut clock = Clock::new(Duration::from_secs(60));
+        assert!(!clock.running());
+        assert_eq!(clock.elapsed(), Duration::from_secs(60));
+
+        clock.start();
+        assert!(clock.running());
+        std::thread::sleep(Duration::from_millis(20));
+        clock.start();
+        clock.pause();
+        assert!(!clock.running());
+        let paused = clock.elapsed();
+        assert!(paused >= Duration::from_millis(60_020));
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(clock.elapsed(), paused);
+        clock.pause();
+        assert_eq!(clock.elapsed(), paused);
+    }
+
+    #[test]
+    fn formats_minutes_and_hours() {
+        assert_eq!(format_time(Duration::ZERO), "0:00");
+        assert_eq!(format_time(Duration::from_millis(59_999)), "0:59");
+        assert_eq!(format_time(Duration::from_secs(754)), "12:34");
+        assert_eq!(format_time(Duration::from_secs(3600)), "1:00:00");
+        assert_eq!(format_time(Duration::from_secs(36_000 + 61)), "10:01:01");
+    }
+}
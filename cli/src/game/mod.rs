@@ -1,10 +1,298 @@
+mod clock;
+mod recovery;
+mod stats;
+
+pub use clock::{format_time, Clock};
+pub use recovery::{Mode, Recovery, AUTOSAVE_INTERVAL};
+pub use stats::{Record, Stats, Summary, DAY};
+
+use dirs::data_dir;
+use merge::Merge;
 use serde::{Deserialize, Serialize};
-use sudokube::model::Grid;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use sudokube::{
+    format::FileFormat,
+    model::{Coordinate, Grid, Notes},
+};
 
 use crate::config::Config;
 
+/// path of `file` in the sudokube directory of the user data directory
+pub fn data_path(file: &str) -> Option<PathBuf> {
+    let mut buf = data_dir()?;
+    buf.push("sudokube");
+    buf.push(file);
+    Some(buf)
+}
+
+/// a game in progress as saved to file by `play` and `make`
 #[derive(Serialize, Deserialize)]
-struct Game<const N: usize> {
-    pub config: Option<Config>,
-    pub grid: Grid<N>,
+pub struct Game<const N: usize> {
+    pub config: Option<Config>, // per-game overrides of the user config
+    pub grid: Grid<N>,          // givens are the cells that are not editable
+    #[serde(default)]
+    pub notes: Notes<N>,
+    #[serde(default)]
+    pub elapsed: Duration, // time played, the final time once solved
+    #[serde(default)]
+    pub history: Vec<Move>, // entries in the order they were made, undone from the back
+    #[serde(default)]
+    pub hints: usize, // hint steps revealed
+    #[serde(default)]
+    pub mistakes: usize, // entries flagged as incorrect
+    #[serde(default)]
+    pub incorrect: Vec<Coordinate>, // flagged entries, until their value changes
+    #[serde(default)]
+    pub solved: bool,
+    #[serde(default)]
+    pub started: Option<u64>, // unix time first played
+    #[serde(default)]
+    pub id: Option<u64>, // random, identifies its statistics record
+}
+
+/// a single entry, setting a cell from one value to another
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Move {
+    pub coordinate: Coordinate,
+    pub from: Option<u8>,
+    pub to: Option<u8>,
+}
+
+impl<const N: usize> Game<N> {
+    pub fn new(grid: Grid<N>) -> Game<N> {
+        Game {
+            config: None,
+            grid,
+            notes: Notes::new(),
+            elapsed: Duration::ZERO,
+            history: Vec::new(),
+            hints: 0,
+            mistakes: 0,
+            incorrect: Vec::new(),
+            solved: false,
+            started: None,
+            id: None,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Game<N>, String> {
+        serde_json::from_str(json).map_err(|error| format!("Invalid game json: {}", error))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn read(path: &str) -> Result<Game<N>, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|error| format!("Error reading file {}: {}", path, error))?;
+        Game::from_json(&json)
+    }
+
+    pub fn write(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_json())
+            .map_err(|error| format!("Error writing file {}: {}", path, error))
+    }
+
+    ///
+    /// Write the grid to `path` in the format of its extension, as a game (see `write`)
+    /// for json and unknown extensions. Other formats keep values only, as givens.
+    /// In files of several puzzles only the first, the one `make` loads, is replaced.
+    ///
+    pub fn write_grid(&self, path: &str) -> Result<(), String> {
+        let format = match FileFormat::from_extension(path) {
+            Some(FileFormat::Json) | None => return self.write(path),
+            Some(format) => format,
+        };
+
+        let mut grids = vec![self.grid.clone()];
+        if format.is_multi() && Path::new(path).exists() {
+            let content = fs::read_to_string(path)
+                .map_err(|error| format!("Error reading file {}: {}", path, error))?;
+            let existing: Vec<Grid<N>> = format
+                .parse(&content)
+                .map_err(|error| format!("Error reading file {}: {}", path, error))?;
+            grids.extend(existing.into_iter().skip(1));
+        }
+
+        let content = format
+            .format(&grids)
+            .map_err(|error| format!("Error writing file {}: {}", path, error))?;
+        fs::write(path, content).map_err(|error| format!("Error writing file {}: {}", path, error))
+    }
+
+    /// `base` with the overrides of this game applied
+    pub fn config(&self, base: &Config) -> Config {
+        let mut config = base.clone();
+        if let Some(overrides) = &self.config {
+            config.merge(overrides.clone());
+        }
+        config
+    }
+
+    ///
+    /// Set the cell at `coordinate` to `value` and record the move.
+    /// Givens and entries that change nothing are ignored.
+    ///
+    pub fn enter(&mut self, coordinate: Coordinate, value: Option<u8>) -> Option<Move> {
+        let cell = self.grid.get_cell(coordinate);
+        if !cell.editable || cell.value == value {
+            return None;
+        }
+
+        let entry = Move {
+            coordinate,
+            from: cell.value,
+            to: value,
+        };
+        self.grid.set_cell_value(coordinate, value).ok()?;
+        self.history.push(entry);
+        self.incorrect.retain(|flagged| *flagged != coordinate);
+        Some(entry)
+    }
+
+    /// revert the last move, returning it
+    pub fn undo(&mut self) -> Option<Move> {
+        let entry = self.history.pop()?;
+        self.grid
+            .set_cell_value(entry.coordinate, entry.from)
+            .ok()?;
+        self.incorrect
+            .retain(|flagged| *flagged != entry.coordinate);
+        Some(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::GameSource;
+    use sudokube::format::{format_line, parse_line};
+
+    const PUZZLE: &str = "12............43";
+
+    #[test]
+    fn entries_are_undone_in_reverse() {
+        let mut game: Game<4> = Game::new(parse_line(PUZZLE).unwrap());
+        assert_eq!(game.enter(Coordinate(0, 0), Some(2)), None);
+        assert_eq!(game.enter(Coordinate(0, 2), None), None);
+
+        // values are stored from 0, shown from 1
+        let first = game.enter(Coordinate(0, 2), Some(2)).unwrap();
+        assert_eq!((first.from, first.to), (None, Some(2)));
+        let second = game.enter(Coordinate(0, 2), Some(3)).unwrap();
+        assert_eq!((second.from, second.to), (Some(2), Some(3)));
+        assert_eq!(game.enter(Coordinate(0, 2), Some(3)), None);
+        assert_eq!(format_line(&game.grid).unwrap(), "124...........43");
+
+        assert_eq!(game.undo(), Some(second));
+        assert_eq!(format_line(&game.grid).unwrap(), "123...........43");
+        assert_eq!(game.undo(), Some(first));
+        assert_eq!(game.undo(), None);
+        assert_eq!(format_line(&game.grid).unwrap(), PUZZLE);
+    }
+
+    #[test]
+    fn changed_entries_are_no_longer_flagged() {
+        let mut game: Game<4> = Game::new(parse_line(PUZZLE).unwrap());
+        game.enter(Coordinate(0, 2), Some(3));
+        game.enter(Coordinate(1, 0), Some(0));
+        game.incorrect = vec![Coordinate(0, 2), Coordinate(1, 0)];
+        game.enter(Coordinate(0, 2), Some(2));
+        assert_eq!(game.incorrect, vec![Coordinate(1, 0)]);
+        game.undo();
+        assert_eq!(game.incorrect, vec![Coordinate(1, 0)]);
+        game.undo();
+        assert!(game.incorrect.is_empty());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut game: Game<4> = Game::new(parse_line(PUZZLE).unwrap());
+        game.enter(Coordinate(0, 2), Some(3));
+        game.elapsed = Duration::from_secs(75);
+        game.hints = 1;
+        game.mistakes = 2;
+        game.incorrect = vec![Coordinate(0, 2)];
+        game.id = Some(42);
+
+        let read: Game<4> = Game::from_json(&game.to_json()).unwrap();
+        assert_eq!(format_line(&read.grid).unwrap(), "124...........43");
+        assert!(!read.grid.get_cell(Coordinate(0, 0)).editable);
+        assert!(read.grid.get_cell(Coordinate(0, 2)).editable);
+        assert_eq!(read.history, game.history);
+        assert_eq!(read.elapsed, game.elapsed);
+        assert_eq!((read.hints, read.mistakes), (1, 2));
+        assert_eq!(read.incorrect, game.incorrect);
+        assert_eq!(read.id, Some(42));
+    }
+
+    /// path of `file` in the temp directory, apart from other test runs
+    fn temp_path(file: &str) -> String {
+        let file = format!("sudokube-{}-{}", std::process::id(), file);
+        std::env::temp_dir()
+            .join(file)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn reopen(path: &str) -> String {
+        let game: Game<4> = GameSource::File(String::from(path)).load_game().unwrap();
+        format_line(&game.grid).unwrap()
+    }
+
+    #[test]
+    fn grids_are_written_in_the_format_of_the_path() {
+        let mut game: Game<4> = Game::new(parse_line(PUZZLE).unwrap());
+        game.enter(Coordinate(0, 2), Some(2));
+        for file in [
+            "puzzle.sdk",
+            "puzzle.ss",
+            "puzzle.sdm",
+            "puzzle.txt",
+            "puzzle.json",
+        ] {
+            let path = temp_path(file);
+            game.write_grid(&path).unwrap();
+            assert_eq!(reopen(&path), "123...........43", "{}", file);
+            fs::remove_file(&path).unwrap();
+        }
+        // only json keeps entries apart from givens
+        let path = temp_path("entries.json");
+        game.write_grid(&path).unwrap();
+        let read: Game<4> = Game::read(&path).unwrap();
+        assert!(read.grid.get_cell(Coordinate(0, 2)).editable);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn other_puzzles_of_a_collection_are_kept() {
+        let path = temp_path("collection.sdm");
+        fs::write(&path, "1200000000000043\n0004000000000000\n").unwrap();
+        let game: Game<4> = Game::new(parse_line("1...............").unwrap());
+        game.write_grid(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "1000000000000000\n0004000000000000\n"
+        );
+        assert_eq!(reopen(&path), "1...............");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn older_saves_read_with_defaults() {
+        let grid: Grid<4> = parse_line(PUZZLE).unwrap();
+        let json = format!(r#"{{"config":null,"grid":{}}}"#, grid.to_json());
+        let game: Game<4> = Game::from_json(&json).unwrap();
+        assert_eq!(format_line(&game.grid).unwrap(), PUZZLE);
+        assert!(game.history.is_empty() && game.incorrect.is_empty());
+        assert_eq!(game.elapsed, Duration::ZERO);
+        assert!(!game.solved);
+        assert_eq!((game.started, game.id), (None, None));
+        assert!(Game::<4>::from_json("{}").is_err());
+    }
 }
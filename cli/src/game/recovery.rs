@@ -0,0 +1,64 @@
+use super::{data_path, Game};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{fs, path::PathBuf, time::Duration};
+
+/// time after which a session without changes is autosaved
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// interactive mode a session was recovered from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    Play,
+    Make,
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Mode::Play => write!(f, "play"),
+            Mode::Make => write!(f, "make"),
+        }
+    }
+}
+
+///
+/// Unfinished session, autosaved under the user data directory
+/// and removed once the session ends normally.
+///
+#[derive(Deserialize)]
+pub struct Recovery<const N: usize> {
+    pub mode: Mode,
+    pub path: Option<String>, // file the session saves to
+    pub game: Game<N>,
+}
+
+impl<const N: usize> Recovery<N> {
+    pub fn file() -> Option<PathBuf> {
+        data_path("recovery.json")
+    }
+
+    /// autosave `game`, errors are ignored as there is nothing the session can do about them
+    pub fn save(mode: Mode, path: Option<&str>, game: &Game<N>) {
+        if let Some(file) = Self::file() {
+            let recovery = json!({ "mode": mode, "path": path, "game": game });
+            if let Some(dir) = file.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let _ = fs::write(file, recovery.to_string());
+        }
+    }
+
+    /// the unfinished session, if any
+    pub fn load() -> Option<Recovery<N>> {
+        let json = fs::read_to_string(Self::file()?).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn clear() {
+        if let Some(file) = Self::file() {
+            let _ = fs::remove_file(file);
+        }
+    }
+}
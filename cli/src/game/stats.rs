@@ -0,0 +1,333 @@
+use super::{data_path, Game};
+use rand::random;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use sudokube::{
+    analysis::{givens, grade},
+    model::Difficulty,
+};
+
+/// seconds in a day, streaks count UTC days
+pub const DAY: u64 = 24 * 60 * 60;
+
+/// a game played, as recorded in the statistics
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Record {
+    pub id: u64,                // id of the game
+    pub started: u64,           // unix time in seconds
+    pub completed: Option<u64>, // unix time in seconds
+    pub size: usize,
+    pub difficulty: Difficulty,
+    pub time: Option<u64>, // seconds played until solved
+    pub hints: usize,
+    pub mistakes: usize,
+}
+
+/// completed games and times of one size and difficulty
+#[derive(Serialize, Debug)]
+pub struct Summary {
+    pub size: usize,
+    pub difficulty: Difficulty,
+    pub started: usize,
+    pub completed: usize,
+    pub best: Option<u64>,
+    pub average: Option<u64>,
+    pub hints: usize,
+    pub mistakes: usize,
+}
+
+/// consecutive days with a completed game
+#[derive(Serialize, Debug, Default)]
+pub struct Streak {
+    pub current: usize, // ending today or yesterday
+    pub longest: usize,
+}
+
+///
+/// Games played by the user, kept under the user data directory.
+/// Recording is best effort like autosaves, a statistics file that cannot be read is left alone.
+///
+#[derive(Serialize, Deserialize, Default)]
+pub struct Stats {
+    pub games: Vec<Record>, // in the order they were started
+}
+
+impl Stats {
+    pub fn file() -> Option<PathBuf> {
+        data_path("stats.json")
+    }
+
+    /// the statistics so far, empty if none were recorded yet
+    pub fn load() -> Result<Stats, String> {
+        let file = Self::file().ok_or("No user data directory")?;
+        match fs::read_to_string(&file) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|error| format!("Invalid statistics in {}: {}", file.display(), error)),
+            Err(_) if !file.exists() => Ok(Stats::default()),
+            Err(error) => Err(format!("Error reading {}: {}", file.display(), error)),
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let file = Self::file().ok_or("No user data directory")?;
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+        fs::write(&file, serde_json::to_string(self).unwrap())
+            .map_err(|error| format!("Error writing {}: {}", file.display(), error))
+    }
+
+    /// record `game` as started, giving it an id so resuming it later does not count again
+    pub fn start<const N: usize>(game: &mut Game<N>) {
+        game.started = Some(now());
+        game.id = Some(random());
+        if let Ok(mut stats) = Stats::load() {
+            stats.games.push(record(game));
+            let _ = stats.save();
+        }
+    }
+
+    ///
+    /// Record `game` as solved, returns whether its time is a new best:
+    /// faster than every earlier one of the same size and difficulty, or the first.
+    ///
+    pub fn complete<const N: usize>(game: &Game<N>) -> bool {
+        match Stats::load() {
+            Ok(mut stats) => {
+                let best = stats.record_completion(game, now());
+                let _ = stats.save();
+                best
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// record `game` as solved at unix time `completed`, see `complete`
+    fn record_completion<const N: usize>(&mut self, game: &Game<N>, completed: u64) -> bool {
+        let index = match self
+            .games
+            .iter()
+            .position(|record| Some(record.id) == game.id)
+        {
+            Some(index) => index,
+            // started before statistics were kept
+            None => {
+                self.games.push(record(game));
+                self.games.len() - 1
+            }
+        };
+
+        let record = &mut self.games[index];
+        let time = game.elapsed.as_secs();
+        record.completed = Some(completed);
+        record.time = Some(time);
+        record.hints = game.hints;
+        record.mistakes = game.mistakes;
+
+        let (size, difficulty) = (record.size, record.difficulty);
+        self.games
+            .iter()
+            .enumerate()
+            .filter(|(i, record)| {
+                *i != index && record.size == size && record.difficulty == difficulty
+            })
+            .filter_map(|(_, record)| record.time)
+            .all(|best| time < best)
+    }
+
+    /// summaries by size, then difficulty
+    pub fn summaries(&self) -> Vec<Summary> {
+        let mut groups: BTreeMap<(usize, Difficulty), Vec<&Record>> = BTreeMap::new();
+        for record in &self.games {
+            groups
+                .entry((record.size, record.difficulty))
+                .or_default()
+                .push(record);
+        }
+
+        groups
+            .into_iter()
+            .map(|((size, difficulty), records)| {
+                let times: Vec<u64> = records.iter().filter_map(|record| record.time).collect();
+                Summary {
+                    size,
+                    difficulty,
+                    started: records.len(),
+                    completed: times.len(),
+                    best: times.iter().min().copied(),
+                    average: match times.len() {
+                        0 => None,
+                        count => Some(times.iter().sum::<u64>() / count as u64),
+                    },
+                    hints: records.iter().map(|record| record.hints).sum(),
+                    mistakes: records.iter().map(|record| record.mistakes).sum(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn streak(&self) -> Streak {
+        self.streak_on(now() / DAY)
+    }
+
+    /// streaks as of day `today`, in days since the unix epoch
+    fn streak_on(&self, today: u64) -> Streak {
+        let mut days: Vec<u64> = self
+            .games
+            .iter()
+            .filter_map(|record| record.completed)
+            .map(|completed| completed / DAY)
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+
+        let mut streak = Streak::default();
+        let mut run = 0;
+        let mut last: Option<u64> = None;
+        for day in days {
+            run = match last {
+                Some(last) if last + 1 == day => run + 1,
+                _ => 1,
+            };
+            streak.longest = streak.longest.max(run);
+            last = Some(day);
+        }
+        if matches!(last, Some(last) if last + 1 >= today) {
+            streak.current = run;
+        }
+        streak
+    }
+}
+
+/// record of a game that was not completed yet
+fn record<const N: usize>(game: &Game<N>) -> Record {
+    Record {
+        id: game.id.unwrap_or_else(random),
+        started: game.started.unwrap_or_else(now),
+        completed: None,
+        size: N,
+        difficulty: grade(&givens(&game.grid)),
+        time: None,
+        hints: game.hints,
+        mistakes: game.mistakes,
+    }
+}
+
+/// unix time in seconds
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use sudokube::format::parse_line;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    /// record of a game of the default size and difficulty completed on `day`
+    fn completed_on(day: u64) -> Record {
+        Record {
+            id: day,
+            started: day * DAY,
+            completed: Some(day * DAY + 600),
+            size: 9,
+            difficulty: Difficulty::Easy,
+            time: Some(600),
+            hints: 0,
+            mistakes: 0,
+        }
+    }
+
+    fn streak_on(days: &[u64], today: u64) -> (usize, usize) {
+        let stats = Stats {
+            games: days.iter().map(|day| completed_on(*day)).collect(),
+        };
+        let streak = stats.streak_on(today);
+        (streak.current, streak.longest)
+    }
+
+    #[test]
+    fn streaks_count_consecutive_days() {
+        assert_eq!(streak_on(&[], 100), (0, 0));
+        assert_eq!(streak_on(&[100], 100), (1, 1));
+        // a streak ending yesterday can still be continued today
+        assert_eq!(streak_on(&[98, 99], 100), (2, 2));
+        assert_eq!(streak_on(&[97, 98], 100), (0, 2));
+        // several games a day count once
+        assert_eq!(streak_on(&[90, 91, 92, 92, 99, 100], 100), (2, 3));
+        assert_eq!(streak_on(&[100, 92, 90, 91], 100), (1, 3));
+
+        let mut unfinished = completed_on(101);
+        unfinished.completed = None;
+        let stats = Stats {
+            games: vec![completed_on(100), unfinished],
+        };
+        assert_eq!(stats.streak_on(101).current, 1);
+    }
+
+    #[test]
+    fn first_and_faster_times_are_best() {
+        let grid = parse_line(PUZZLE).unwrap();
+        let difficulty = grade(&givens(&grid));
+        let mut stats = Stats::default();
+        let mut play = |id: u64, seconds: u64| {
+            let mut game: Game<9> = Game::new(grid.clone());
+            game.id = Some(id);
+            stats.games.push(record(&game));
+            game.elapsed = Duration::from_secs(seconds);
+            stats.record_completion(&game, id * DAY)
+        };
+
+        assert!(play(1, 300));
+        assert!(!play(2, 400));
+        assert!(!play(3, 300));
+        assert!(play(4, 200));
+
+        let summaries = stats.summaries();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].difficulty, difficulty);
+        assert_eq!(summaries[0].completed, 4);
+        assert_eq!(summaries[0].best, Some(200));
+        assert_eq!(summaries[0].average, Some(300));
+    }
+
+    #[test]
+    fn completions_update_the_record_of_the_game() {
+        let mut game: Game<9> = Game::new(parse_line(PUZZLE).unwrap());
+        let mut stats = Stats::default();
+        game.id = Some(7);
+        game.started = Some(DAY);
+        stats.games.push(record(&game));
+        // another game started at the same time
+        let mut other: Game<9> = Game::new(parse_line(PUZZLE).unwrap());
+        other.id = Some(8);
+        other.started = Some(DAY);
+        stats.games.push(record(&other));
+
+        game.elapsed = Duration::from_secs(90);
+        game.hints = 2;
+        stats.record_completion(&game, 2 * DAY);
+        assert_eq!(stats.games.len(), 2);
+        assert_eq!(stats.games[0].time, Some(90));
+        assert_eq!(stats.games[0].completed, Some(2 * DAY));
+        assert_eq!(stats.games[0].hints, 2);
+        assert_eq!(stats.games[1].time, None);
+
+        // games started before statistics were kept get a record on completion
+        let unknown: Game<9> = Game::new(parse_line(PUZZLE).unwrap());
+        stats.record_completion(&unknown, 2 * DAY);
+        assert_eq!(stats.games.len(), 3);
+        assert_eq!(stats.games[2].completed, Some(2 * DAY));
+    }
+}
@@ -1,30 +1,30 @@
 use clap::{crate_authors, crate_description, crate_license, crate_name, crate_version, App, Arg};
 
-use crossterm::{
-    cursor::{DisableBlinking, EnableBlinking, MoveTo},
-    event::{
-        read, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
-    },
-    execute,
-    style::Print,
-    terminal::{
-        disable_raw_mode, enable_raw_mode, size, EnterAlternateScreen, LeaveAlternateScreen,
-    },
-};
 use merge::Merge;
-use std::io::stdout;
+use std::{
+    io::{stdin, stdout, Write},
+    process,
+};
 
-use sudokube::model::{Coordinate, Grid};
+use sudokube::{
+    error::SudokuError,
+    format::{encode_share_code, FileFormat},
+    generator::{Generator, GeneratorOptions},
+    model::Grid,
+    solver::Uniqueness,
+};
 
+pub mod command;
 pub mod config;
 pub mod display;
 pub mod enums;
 pub mod game;
 pub mod lib;
+pub mod source;
 
-use crate::display::DGrid;
-use crate::enums::Navigation;
-use crate::{config::Config, display::render_coordinate_guide};
+use crate::config::Config;
+use crate::game::{Game, Mode, Recovery};
+use crate::source::GameSource;
 
 fn main() {
     let matches = App::new(crate_name!())
@@ -45,7 +45,7 @@ fn main() {
         .subcommand(
             App::new("make").about("create/edit a game").arg(
                 Arg::new("path")
-                    .about("filepath to game to edit, otherwise will create new game")
+                    .about("filepath or puzzle line of game to edit, otherwise creates a new game")
                     .index(1)
                     .required(false),
             ),
@@ -55,145 +55,568 @@ fn main() {
                 .about("attempt to solve a given game")
                 .arg(
                     Arg::new("path")
-                        .about("filepath to game to solve")
+                        .about("filepath or puzzle line of game to solve")
+                        .index(1)
+                        .required_unless_present("batch"),
+                )
+                .arg(
+                    Arg::new("batch")
+                        .about("solve every puzzle of a collection file on all CPU cores")
+                        .takes_value(true)
+                        .long("batch")
+                        .conflicts_with("path")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("format")
+                        .about("output format of the solution")
+                        .takes_value(true)
+                        .short('f')
+                        .long("format")
+                        .possible_values(&command::SOLVE_FORMATS)
+                        .default_value("pretty")
+                        .conflicts_with("batch")
+                        .required(false),
+                ),
+        )
+        .subcommand(
+            App::new("grade")
+                .about("grade every puzzle of a collection file on all CPU cores")
+                .arg(
+                    Arg::new("path")
+                        .about("filepath to puzzle collection (lines or json lines)")
                         .index(1)
                         .required(true),
                 ),
         )
         .subcommand(
-            App::new("play").about("play a sudoku game").arg(
+            App::new("check")
+                .about("report conflicts and correctness of a game, exits with 2 if invalid")
+                .arg(
+                    Arg::new("path")
+                        .about("filepath or puzzle line of game to check")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("format")
+                        .about("output format of the report")
+                        .takes_value(true)
+                        .short('f')
+                        .long("format")
+                        .possible_values(&command::CHECK_FORMATS)
+                        .default_value("text")
+                        .required(false),
+                ),
+        )
+        .subcommand(
+            App::new("play")
+                .about("play a sudoku game")
+                .arg(
+                    Arg::new("path")
+                        .about("filepath or puzzle line of game to load, otherwise generated")
+                        .index(1)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("difficulty")
+                        .about("difficulty (or band, e.g. easy..hard) of a generated puzzle")
+                        .takes_value(true)
+                        .short('d')
+                        .long("difficulty")
+                        .default_value("easy..medium")
+                        .conflicts_with("path"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .about("file to save the game to, defaults to the file of a resumed game")
+                        .takes_value(true)
+                        .short('o')
+                        .long("output"),
+                ),
+        )
+        .subcommand(
+            App::new("generate")
+                .about("generate puzzles as a collection")
+                .arg(
+                    Arg::new("size")
+                        .about("grid size")
+                        .takes_value(true)
+                        .long("size")
+                        .possible_values(&command::GENERATE_SIZES)
+                        .default_value("9")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("count")
+                        .about("number of puzzles")
+                        .takes_value(true)
+                        .short('n')
+                        .long("count")
+                        .default_value("1")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("difficulty")
+                        .about("difficulty or range of difficulties, e.g. medium or easy..hard")
+                        .takes_value(true)
+                        .short('d')
+                        .long("difficulty")
+                        .default_value("easy..expert")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("symmetry")
+                        .about("symmetry of the clues, e.g. rotational, left-right or diagonal")
+                        .takes_value(true)
+                        .short('s')
+                        .long("symmetry")
+                        .default_value("none")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("clues")
+                        .about("number or range of clues, e.g. 25..30, otherwise minimal")
+                        .takes_value(true)
+                        .long("clues")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .about("seed for reproducible output, otherwise random")
+                        .takes_value(true)
+                        .long("seed")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("format")
+                        .about("collection format, otherwise inferred from the output file")
+                        .takes_value(true)
+                        .short('f')
+                        .long("format")
+                        .possible_values(&command::COLLECTION_FORMATS)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("output")
+                        .about("file to write to, otherwise prints to stdout")
+                        .takes_value(true)
+                        .short('o')
+                        .long("output")
+                        .required(false),
+                ),
+        )
+        .subcommand(
+            App::new("share").about("print a share code of a game").arg(
                 Arg::new("path")
-                    .about("filepath to custom game to load, otherwise will be generated")
+                    .about("filepath or puzzle line of game to share")
                     .index(1)
-                    .required(false),
+                    .required(true),
             ),
         )
+        .subcommand(
+            App::new("export")
+                .about("export a game for use by other tools")
+                .arg(
+                    Arg::new("path")
+                        .about("filepath or puzzle line of game to export")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("format")
+                        .about("export format")
+                        .takes_value(true)
+                        .short('f')
+                        .long("format")
+                        .possible_values(&command::EXPORT_FORMATS)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("output")
+                        .about("file to write to, otherwise prints to stdout")
+                        .takes_value(true)
+                        .short('o')
+                        .long("output")
+                        .required(false),
+                ),
+        )
+        .subcommand(
+            App::new("convert")
+                .about("convert a puzzle file or collection to another format")
+                .arg(
+                    Arg::new("input")
+                        .about("filepath of puzzle file or collection")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("output")
+                        .about("filepath to write to, - for stdout")
+                        .index(2)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("from")
+                        .about("input format, otherwise inferred from its extension or content")
+                        .takes_value(true)
+                        .long("from")
+                        .possible_values(&command::CONVERT_FORMATS)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("to")
+                        .about("output format, otherwise inferred from its extension")
+                        .takes_value(true)
+                        .long("to")
+                        .possible_values(&command::CONVERT_FORMATS)
+                        .required(false),
+                ),
+        )
+        .subcommand(
+            App::new("dedupe")
+                .about("report puzzles of a collection that are the same up to symmetry")
+                .arg(
+                    Arg::new("path")
+                        .about("filepath to puzzle collection (lines or json lines)")
+                        .index(1)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("all")
+                        .about("also list puzzles without duplicates")
+                        .long("all")
+                        .required(false),
+                ),
+        )
+        .subcommand(
+            App::new("stats")
+                .about("print statistics and recent history of the games played")
+                .arg(
+                    Arg::new("history")
+                        .about("number of recent games to list, 0 for all")
+                        .takes_value(true)
+                        .short('n')
+                        .long("history")
+                        .default_value("10")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("format")
+                        .about("output format of the statistics")
+                        .takes_value(true)
+                        .short('f')
+                        .long("format")
+                        .possible_values(&command::STATS_FORMATS)
+                        .default_value("text")
+                        .required(false),
+                ),
+        )
         .get_matches();
 
     let mut config = Config::default();
     if let Some(ref file) = matches.value_of("config") {
         config.merge(Config::read(file));
 
-        // debug
-        config.write(file).unwrap();
+        // interactive sessions write the defaults missing from the file, so they can be edited
+        if matches!(matches.subcommand_name(), Some("play") | Some("make")) {
+            if let Err(error) = config.write(file) {
+                eprintln!("Error writing config file {}: {}", file, error);
+            }
+        }
     }
 
     match matches.subcommand() {
         Some(("make", clone_matches)) => {
-            let game_path = match clone_matches.value_of("path") {
-                Some(game) => game,
-                None => "<no_game_path_provided>",
+            if let Some(recovery) = offer_recovery::<9>() {
+                return resume(recovery, &config);
+            }
+            let (game, save_path): (Game<9>, Option<&str>) = match clone_matches.value_of("path") {
+                Some(arg) => match GameSource::from_arg(arg) {
+                    Ok(source @ GameSource::File(_)) => (load_game(&source), Some(arg)),
+                    Ok(source) => (load_game(&source), None),
+                    // a new game to create at that path
+                    Err(_) if GameSource::is_new_file(arg) => (Game::new(Grid::new()), Some(arg)),
+                    Err(error) => {
+                        eprintln!("Error loading game: {}", error);
+                        process::exit(1);
+                    }
+                },
+                None => (Game::new(Grid::new()), None),
+            };
+            make(game, save_path, &config);
+        }
+        Some(("solve", clone_matches)) => {
+            if let Some(path) = clone_matches.value_of("batch") {
+                if let Err(error) = command::solve_batch_file::<9>(path) {
+                    eprintln!("Error solving collection {}: {}", path, error);
+                    process::exit(1);
+                }
+                return;
+            }
+
+            let source = match clone_matches.value_of("path") {
+                Some(arg) => game_source(arg),
+                None => panic!("Path to game must be provided!"),
             };
-            println!("Create/Edit game at {}", game_path);
-
-            const GRID_SIZE: usize = 9;
-            let mut grid: Grid<GRID_SIZE> = Grid::new();
-            let mut d_grid: DGrid<GRID_SIZE> = DGrid::new(&grid, Coordinate(1, 2));
-
-            enable_raw_mode().unwrap();
-
-            let mut stdout = stdout();
-            execute!(
-                stdout,
-                EnableMouseCapture,
-                EnterAlternateScreen,
-                DisableBlinking,
-            )
-            .unwrap();
-
-            render_coordinate_guide(Coordinate(0, 0), 9);
-
-            d_grid.render(&grid, &config);
-            d_grid.navigate_to(
-                &grid,
-                &config,
-                Coordinate(GRID_SIZE as u8 / 2, GRID_SIZE as u8 / 2),
-            );
-
-            let key_binding = &config.key_binding();
-            let navigation_key_events = &key_binding.navigation();
-
-            loop {
-                let event = read().unwrap();
-
-                // navigation event;
-                if event == navigation_key_events.left().crossterm() {
-                    d_grid.navigate(&grid, &config, Navigation::Col(-1));
-                } else if event == navigation_key_events.up().crossterm() {
-                    d_grid.navigate(&grid, &config, Navigation::Row(-1));
-                } else if event == navigation_key_events.right().crossterm() {
-                    d_grid.navigate(&grid, &config, Navigation::Col(1));
-                } else if event == navigation_key_events.down().crossterm() {
-                    d_grid.navigate(&grid, &config, Navigation::Row(1));
-                } else if event == navigation_key_events.next_group().crossterm() {
-                    d_grid.navigate(&grid, &config, Navigation::Group(1));
-                } else if event == navigation_key_events.previous_group().crossterm() {
-                    d_grid.navigate(&grid, &config, Navigation::Group(-1));
-                } else if event == key_binding.quit().crossterm() {
-                    let (cols, rows) = size().unwrap();
-                    execute!(stdout, MoveTo(0, rows), Print("Quitting...".to_string())).unwrap();
-                    println!("Terminal Size ({}, {})", cols, rows);
-                    break;
-                } else if event == key_binding.toggle_context_highlight().crossterm() {
-                    config.toggle_context_highlight();
-                    d_grid.rerender(&grid, &config);
-                } else if event == key_binding.delete().crossterm() {
-                    let old_value = grid.get_cell(d_grid.active).value;
-                    grid.set_cell_value(d_grid.active, None).unwrap();
-                    d_grid.set_value(&grid, &config, d_grid.active, old_value, None);
-                } else if let Event::Key(KeyEvent {
-                    modifiers: KeyModifiers::NONE,
-                    code: KeyCode::Char(c),
-                }) = event
-                {
-                    let mut value: Option<u8> = None;
-                    for i in 0..GRID_SIZE {
-                        let ii = i + 1;
-                        let char = ii.to_string().chars().nth(0).unwrap();
-                        if char == c {
-                            value = Some(i as u8);
+            let grid: Grid<9> = load_grid(&source);
+            let format = clone_matches.value_of("format").unwrap();
+            match command::solve(&grid, format, &config) {
+                Ok(Uniqueness::Unique) => {}
+                Ok(Uniqueness::None) => process::exit(command::EXIT_UNSOLVABLE),
+                Ok(Uniqueness::Multiple) => process::exit(command::EXIT_MULTIPLE_SOLUTIONS),
+                Err(error) => {
+                    eprintln!("Error solving game from {}: {}", source, error);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(("grade", clone_matches)) => {
+            let path = clone_matches.value_of("path").unwrap();
+            if let Err(error) = command::grade_batch_file::<9>(path) {
+                eprintln!("Error grading collection {}: {}", path, error);
+                process::exit(1);
+            }
+        }
+        Some(("check", clone_matches)) => {
+            let source = match clone_matches.value_of("path") {
+                Some(arg) => game_source(arg),
+                None => panic!("Path to game must be provided!"),
+            };
+            let grid: Grid<9> = load_grid(&source);
+            match command::check(&grid, clone_matches.value_of("format").unwrap()) {
+                Ok(true) => {}
+                Ok(false) => process::exit(command::EXIT_INVALID),
+                Err(error) => {
+                    eprintln!("Error checking game from {}: {}", source, error);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(("play", clone_matches)) => {
+            if let Some(recovery) = offer_recovery::<9>() {
+                return resume(recovery, &config);
+            }
+            let mut save_path = clone_matches.value_of("output").map(String::from);
+            let game: Game<9> = match clone_matches.value_of("path") {
+                Some(arg) => {
+                    let source = game_source(arg);
+                    if let GameSource::File(path) = &source {
+                        // resumed games are saved back to their file
+                        if save_path.is_none() && Game::<9>::read(path).is_ok() {
+                            save_path = Some(path.clone());
                         }
                     }
-                    if let Some(_) = value {
-                        let old_value = grid.get_cell(d_grid.active).value;
-                        grid.set_cell_value(d_grid.active, value).unwrap();
-                        d_grid.set_value(&grid, &config, d_grid.active, old_value, value);
+                    load_game(&source)
+                }
+                None => {
+                    let difficulty = clone_matches.value_of("difficulty").unwrap();
+                    let options = GeneratorOptions {
+                        difficulty: command::parse_range(difficulty).unwrap_or_else(|error| {
+                            eprintln!("Error generating game: {}", error);
+                            process::exit(1);
+                        }),
+                        ..GeneratorOptions::default()
+                    };
+                    match Generator::from_seed(rand::random(), options).generate() {
+                        Ok(generated) => Game::new(generated.puzzle),
+                        Err(error) => {
+                            eprintln!("Error generating game: {}", error);
+                            process::exit(1);
+                        }
                     }
                 }
+            };
+            // play saves progress, only games keep it
+            if let Some(path) = &save_path {
+                if !matches!(
+                    FileFormat::from_extension(path),
+                    Some(FileFormat::Json) | None
+                ) {
+                    eprintln!(
+                        "Error: games are saved as json, {} is not a json file",
+                        path
+                    );
+                    process::exit(1);
+                }
+            }
+            command::play(game, save_path.as_deref(), &config);
+        }
+        Some(("generate", clone_matches)) => {
+            let exit = |error: String| -> ! {
+                eprintln!("Error generating puzzles: {}", error);
+                process::exit(1);
+            };
+
+            let mut options = GeneratorOptions::default();
+            let difficulty = clone_matches.value_of("difficulty").unwrap();
+            options.difficulty = command::parse_range(difficulty).unwrap_or_else(|e| exit(e));
+            let symmetry = clone_matches.value_of("symmetry").unwrap();
+            options.symmetry = symmetry
+                .parse()
+                .unwrap_or_else(|e: SudokuError| exit(e.to_string()));
+            if let Some(clues) = clone_matches.value_of("clues") {
+                options.clues = command::parse_range(clues).unwrap_or_else(|e| exit(e));
             }
 
-            execute!(
-                stdout,
-                DisableMouseCapture,
-                LeaveAlternateScreen,
-                EnableBlinking,
-            )
-            .unwrap();
-            // execute!(stdout, DisableMouseCapture).unwrap();
-
-            disable_raw_mode().unwrap();
-            // TODO: sudoku generation
+            let count: usize = clone_matches
+                .value_of("count")
+                .unwrap()
+                .parse()
+                .unwrap_or_else(|_| exit(String::from("Count must be a positive number")));
+            let seed: u64 = match clone_matches.value_of("seed") {
+                Some(seed) => seed
+                    .parse()
+                    .unwrap_or_else(|_| exit(String::from("Seed must be a positive number"))),
+                None => {
+                    let seed = rand::random();
+                    eprintln!("Seed: {}", seed);
+                    seed
+                }
+            };
+            let output = clone_matches.value_of("output");
+            let format = command::collection_format(clone_matches.value_of("format"), output);
+
+            let result = match clone_matches.value_of("size").unwrap() {
+                "4" => command::generate::<4>(options, count, seed, format, output),
+                "16" => command::generate::<16>(options, count, seed, format, output),
+                _ => command::generate::<9>(options, count, seed, format, output),
+            };
+            if let Err(error) = result {
+                exit(error);
+            }
         }
-        Some(("solve", clone_matches)) => {
-            let game_path = match clone_matches.value_of("path") {
-                Some(path) => path,
+        Some(("share", clone_matches)) => {
+            let source = match clone_matches.value_of("path") {
+                Some(arg) => game_source(arg),
+                None => panic!("Path to game must be provided!"),
+            };
+            let game: Game<9> = load_game(&source);
+            match encode_share_code(&game.grid, Some(&game.notes)) {
+                Ok(code) => {
+                    if let Err(error) = command::print_output(&format!("{}\n", code)) {
+                        eprintln!("Error sharing game from {}: {}", source, error);
+                        process::exit(1);
+                    }
+                }
+                Err(error) => {
+                    eprintln!("Error sharing game from {}: {}", source, error);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(("export", clone_matches)) => {
+            let source = match clone_matches.value_of("path") {
+                Some(arg) => game_source(arg),
                 None => panic!("Path to game must be provided!"),
             };
-            println!("Solving game at {}", game_path);
-            // TODO: sudoku solving
+            let grid: Grid<9> = load_grid(&source);
+            let format = clone_matches.value_of("format").unwrap();
+            if let Err(error) = command::export(&grid, format, clone_matches.value_of("output")) {
+                eprintln!("Error exporting game from {}: {}", source, error);
+                process::exit(1);
+            }
         }
-        Some(("play", clone_matches)) => {
-            // TODO: sudoku playing
-            // - error checking
-            // - type tracking
-
-            let game_path = match clone_matches.value_of("path") {
-                Some(game) => game,
-                None => "<no_game_path_provided:should_auto_generate>",
+        Some(("convert", clone_matches)) => {
+            let input = clone_matches.value_of("input").unwrap();
+            let output = clone_matches.value_of("output").unwrap();
+            if let Err(error) = command::convert::<9>(
+                input,
+                output,
+                clone_matches.value_of("from"),
+                clone_matches.value_of("to"),
+            ) {
+                eprintln!("Error converting {}: {}", input, error);
+                process::exit(1);
+            }
+        }
+        Some(("dedupe", clone_matches)) => {
+            let path = match clone_matches.value_of("path") {
+                Some(path) => path,
+                None => panic!("Path to collection must be provided!"),
             };
-            println!("Playing game at {}", game_path);
+            if let Err(error) = command::dedupe::<9>(path, clone_matches.is_present("all")) {
+                eprintln!("Error deduplicating {}: {}", path, error);
+                process::exit(1);
+            }
+        }
+        Some(("stats", clone_matches)) => {
+            let exit = |error: String| -> ! {
+                eprintln!("Error reading statistics: {}", error);
+                process::exit(1);
+            };
+
+            let history: usize = clone_matches
+                .value_of("history")
+                .unwrap()
+                .parse()
+                .unwrap_or_else(|_| exit(String::from("History must be a positive number")));
+            if let Err(error) = command::stats(clone_matches.value_of("format").unwrap(), history) {
+                exit(error);
+            }
         }
         None => println!("Doing nothing..."),
         _ => unreachable!(),
     }
 }
+
+fn make<const N: usize>(game: Game<N>, save_path: Option<&str>, config: &Config) {
+    if let Err(error) = command::make(game, save_path, config) {
+        eprintln!("Error saving game: {}", error);
+        process::exit(1);
+    }
+}
+
+/// offer to restore the unfinished session of an earlier `play` or `make`
+fn offer_recovery<const N: usize>() -> Option<Recovery<N>> {
+    let recovery = Recovery::load()?;
+    match &recovery.path {
+        Some(path) => print!(
+            "Restore unfinished {} session of {}? [Y/n] ",
+            recovery.mode, path
+        ),
+        None => print!("Restore unfinished {} session? [Y/n] ", recovery.mode),
+    }
+    stdout().flush().ok()?;
+
+    let mut answer = String::new();
+    stdin().read_line(&mut answer).ok()?;
+    match answer.trim().to_lowercase().as_str() {
+        "" | "y" | "yes" => Some(recovery),
+        _ => {
+            Recovery::<N>::clear();
+            None
+        }
+    }
+}
+
+fn resume<const N: usize>(recovery: Recovery<N>, config: &Config) {
+    let path = recovery.path.as_deref();
+    match recovery.mode {
+        Mode::Play => {
+            command::play(recovery.game, path, config);
+        }
+        Mode::Make => make(recovery.game, path, config),
+    }
+}
+
+fn game_source(arg: &str) -> GameSource {
+    GameSource::from_arg(arg).unwrap_or_else(|error| {
+        eprintln!("Error loading game: {}", error);
+        process::exit(1);
+    })
+}
+
+fn load_grid<const N: usize>(source: &GameSource) -> Grid<N> {
+    load_game(source).grid
+}
+
+fn load_game<const N: usize>(source: &GameSource) -> Game<N> {
+    match source.load_game() {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("Error loading game from {}: {}", source, error);
+            process::exit(1);
+        }
+    }
+}
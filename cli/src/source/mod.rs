@@ -0,0 +1,170 @@
+use crate::game::Game;
+use std::{fs, path::Path};
+use sudokube::{
+    format::{decode_share_code, parse_fpuzzles, FileFormat},
+    model::Grid,
+};
+
+/// where a game is loaded from, as given on the command line
+#[derive(Debug, Clone)]
+pub enum GameSource {
+    File(String), // path to a puzzle file, see `FileFormat`
+    Line(String), // literal puzzle line `53..7....6..195...`, share code or compressed description
+}
+
+impl GameSource {
+    ///
+    /// Treat `arg` as a path if such file exists, otherwise as a literal puzzle line.
+    /// Arguments that read as a path (see `names_file`) of a missing file are an error,
+    /// so a mistyped path is not parsed as a puzzle.
+    ///
+    pub fn from_arg(arg: &str) -> Result<Self, String> {
+        if Path::new(arg).is_file() {
+            Ok(GameSource::File(String::from(arg)))
+        } else if Self::names_file(arg) && FileFormat::sniff(arg) == FileFormat::Line {
+            Err(format!("No such file: {}", arg))
+        } else {
+            Ok(GameSource::Line(String::from(arg)))
+        }
+    }
+
+    ///
+    /// Whether `arg`, naming no existing file, reads as the path of a file to create:
+    /// it names a file (see `names_file`) in a directory that exists.
+    ///
+    pub fn is_new_file(arg: &str) -> bool {
+        let directory = match Path::new(arg).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.is_dir(),
+            _ => true,
+        };
+        Self::names_file(arg) && directory
+    }
+
+    ///
+    /// Whether `arg` reads as a path rather than a puzzle: it has a path separator or a file
+    /// extension. Puzzle lines are full of dots, so only extensions with a lowercase letter count.
+    ///
+    fn names_file(arg: &str) -> bool {
+        let extension = Path::new(arg)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        arg.chars().any(std::path::is_separator)
+            || extension.chars().any(|c| c.is_ascii_lowercase())
+    }
+
+    /// load the game grid, for multi-puzzle files the first puzzle is used
+    pub fn load<const N: usize>(&self) -> Result<Grid<N>, String> {
+        Ok(self.load_game()?.grid)
+    }
+
+    /// resume the saved game, or start a new one on the loaded grid
+    pub fn load_game<const N: usize>(&self) -> Result<Game<N>, String> {
+        let (content, format) = match self {
+            GameSource::File(path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|error| format!("Error reading file {}: {}", path, error))?;
+                let format = FileFormat::detect(path, &content);
+                (content, format)
+            }
+            GameSource::Line(line) => (line.clone(), FileFormat::sniff(line)),
+        };
+
+        if format == FileFormat::Json {
+            if let Ok(game) = Game::from_json(&content) {
+                return Ok(game);
+            }
+        }
+
+        if format == FileFormat::FPuzzles {
+            let puzzle = parse_fpuzzles(&content)
+                .map_err(|error| format!("{} ({} format)", error, format))?;
+            for constraint in &puzzle.unsupported {
+                eprintln!("Warning: unsupported constraint is ignored: {}", constraint);
+            }
+            return Ok(Game::new(puzzle.grid));
+        }
+
+        if format == FileFormat::ShareCode {
+            let shared = decode_share_code(&content)
+                .map_err(|error| format!("{} ({} format)", error, format))?;
+            let mut game = Game::new(shared.grid);
+            if let Some(notes) = shared.notes {
+                game.notes = notes;
+            }
+            return Ok(game);
+        }
+
+        let grids: Vec<Grid<N>> = format
+            .parse(&content)
+            .map_err(|error| format!("{} ({} format)", error, format))?;
+        grids
+            .into_iter()
+            .next()
+            .map(Game::new)
+            .ok_or_else(|| format!("No puzzle found in {}", self))
+    }
+}
+
+impl std::fmt::Display for GameSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GameSource::File(path) => write!(f, "{}", path),
+            GameSource::Line(line) => write!(f, "{}", line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_files_are_not_read_as_puzzles() {
+        let file = std::env::temp_dir().join(format!("sudokube-{}.sdk", std::process::id()));
+        fs::write(&file, "12..\n....\n....\n..43\n").unwrap();
+        let path = file.to_str().unwrap();
+        assert!(matches!(
+            GameSource::from_arg(path),
+            Ok(GameSource::File(_))
+        ));
+        fs::remove_file(&file).unwrap();
+        assert_eq!(
+            GameSource::from_arg(path).err(),
+            Some(format!("No such file: {}", path))
+        );
+        assert!(GameSource::from_arg("nonexist.json").is_err());
+        assert!(GameSource::from_arg("puzzles/nonexist").is_err());
+
+        for line in [
+            "53..7....6..195....98....6.8",
+            "sk-AQQAZ-Yw_1c",
+            "https://f-puzzles.com/?load=N4IgzglgXp",
+        ] {
+            assert!(matches!(
+                GameSource::from_arg(line),
+                Ok(GameSource::Line(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn new_files_are_named_in_existing_directories() {
+        let directory = std::env::temp_dir();
+        let in_directory = directory.join("puzzle.sdk");
+        assert!(GameSource::is_new_file("puzzle.json"));
+        assert!(GameSource::is_new_file("Puzzle.Sdk"));
+        assert!(GameSource::is_new_file(in_directory.to_str().unwrap()));
+        assert!(GameSource::is_new_file("./puzzle"));
+
+        assert!(!GameSource::is_new_file("missing-directory/puzzle.json"));
+        assert!(!GameSource::is_new_file("puzzle"));
+        // puzzle lines, share codes and puzzle urls
+        assert!(!GameSource::is_new_file("53..7....6..195....98....6.8"));
+        assert!(!GameSource::is_new_file("12.3.4..1..."));
+        assert!(!GameSource::is_new_file("sk-AQQAZ-Yw_1c"));
+        assert!(!GameSource::is_new_file(
+            "https://f-puzzles.com/?load=N4IgzglgXp"
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::model::{Coordinate, Grid};
+use crate::solver::{Backtracking, Solver};
+
+/// Two solutions of a grid that has more than one
+pub struct Ambiguity<const N: usize> {
+    pub solutions: [Grid<N>; 2],
+    /// cells filled differently in the two solutions, row by row
+    pub cells: Vec<Coordinate>,
+}
+
+///
+/// Find two solutions of `grid` and where they differ, a clue in one of
+/// those cells rules out at least one of them.
+/// Returns `None` if `grid` has no or exactly one solution.
+///
+pub fn find_ambiguity<const N: usize, S: Solver>(
+    solver: &S,
+    grid: &Grid<N>,
+) -> Option<Ambiguity<N>> {
+    ambiguity(solver.solutions(grid, 2))
+}
+
+///
+/// Like `find_ambiguity`, giving up after visiting `nodes` search nodes.
+/// `None` if the budget ran out before an answer.
+///
+pub fn find_ambiguity_within<const N: usize>(
+    grid: &Grid<N>,
+    nodes: usize,
+) -> Option<Option<Ambiguity<N>>> {
+    Backtracking::solutions_within(grid, 2, nodes).map(ambiguity)
+}
+
+fn ambiguity<const N: usize>(solutions: Vec<Grid<N>>) -> Option<Ambiguity<N>> {
+    let mut solutions = solutions.into_iter();
+    let first = solutions.next()?;
+    let second = solutions.next()?;
+
+    let cells = first
+        .rows
+        .iter()
+        .flat_map(|row| row.cells.iter())
+        .filter(|cell| second.get_cell(cell.coordinate).value != cell.value)
+        .map(|cell| cell.coordinate)
+        .collect();
+
+    Some(Ambiguity {
+        solutions: [first, second],
+        cells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    #[test]
+    fn finds_cells_telling_two_solutions_apart() {
+        // 4 and 8 of r2c8, r2c9, r7c8 and r7c9 can be swapped
+        let mut line: Vec<u8> = SOLUTION.bytes().collect();
+        for index in [16, 17, 61, 62] {
+            line[index] = b'.';
+        }
+        let grid: Grid<9> = parse_line(std::str::from_utf8(&line).unwrap()).unwrap();
+
+        let ambiguity = find_ambiguity(&Backtracking, &grid).unwrap();
+        let [first, second] = &ambiguity.solutions;
+        assert_ne!(format_line(first).unwrap(), format_line(second).unwrap());
+        assert!([first, second]
+            .iter()
+            .any(|solution| format_line(*solution).unwrap() == SOLUTION));
+        assert_eq!(
+            ambiguity.cells,
+            vec![
+                Coordinate(1, 7),
+                Coordinate(1, 8),
+                Coordinate(6, 7),
+                Coordinate(6, 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn unique_and_unsolvable_grids_are_not_ambiguous() {
+        let unique: Grid<9> = parse_line(PUZZLE).unwrap();
+        assert!(find_ambiguity(&Backtracking, &unique).is_none());
+        let none: Grid<9> = parse_line(&format!("55{}", &PUZZLE[2..])).unwrap();
+        assert!(find_ambiguity(&Backtracking, &none).is_none());
+    }
+
+    #[test]
+    fn budgeted_searches_give_up() {
+        let empty: Grid<9> = parse_line(&".".repeat(81)).unwrap();
+        assert!(find_ambiguity_within(&empty, 50).is_none());
+        let ambiguity = find_ambiguity_within(&empty, 10_000).unwrap().unwrap();
+        assert!(!ambiguity.cells.is_empty());
+
+        let unique: Grid<9> = parse_line(PUZZLE).unwrap();
+        assert!(find_ambiguity_within(&unique, 10_000).unwrap().is_none());
+    }
+}
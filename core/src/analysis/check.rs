@@ -0,0 +1,194 @@
+use super::{conflicts, filled_coordinates, givens};
+use crate::model::{Coordinate, Grid};
+use crate::solver::{Solver, Uniqueness};
+use serde::{Deserialize, Serialize};
+use serde_json;
+
+///
+/// Validity of a filled or partially filled grid.
+/// Cells are serialized 1-based as `{"row": 1, "col": 1}`, like `r1c1` in text.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct CheckReport {
+    /// pairs of cells with the same value in a row, column or sub grid
+    #[serde(with = "cell_pairs")]
+    pub conflicts: Vec<[Coordinate; 2]>,
+    /// every cell has a value
+    pub complete: bool,
+    /// number of solutions of the givens
+    pub givens: Uniqueness,
+    /// filled cells that differ from the unique solution of the givens,
+    /// `None` if the givens do not have a unique solution
+    #[serde(with = "cell_list")]
+    pub incorrect: Option<Vec<Coordinate>>,
+}
+
+/// cell of a serialized report, 1-based
+#[derive(Serialize, Deserialize)]
+struct Cell {
+    row: u8,
+    col: u8,
+}
+
+impl From<&Coordinate> for Cell {
+    fn from(coordinate: &Coordinate) -> Cell {
+        Cell {
+            row: coordinate.row() + 1,
+            col: coordinate.col() + 1,
+        }
+    }
+}
+
+impl From<Cell> for Coordinate {
+    fn from(cell: Cell) -> Coordinate {
+        Coordinate(cell.row.saturating_sub(1), cell.col.saturating_sub(1))
+    }
+}
+
+mod cell_pairs {
+    use super::Cell;
+    use crate::model::Coordinate;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        pairs: &[[Coordinate; 2]],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let cells: Vec<[Cell; 2]> = pairs.iter().map(|[a, b]| [a.into(), b.into()]).collect();
+        cells.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<[Coordinate; 2]>, D::Error> {
+        let cells: Vec<[Cell; 2]> = Vec::deserialize(deserializer)?;
+        Ok(cells
+            .into_iter()
+            .map(|[a, b]| [a.into(), b.into()])
+            .collect())
+    }
+}
+
+mod cell_list {
+    use super::Cell;
+    use crate::model::Coordinate;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        coordinates: &Option<Vec<Coordinate>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let cells: Option<Vec<Cell>> = coordinates
+            .as_ref()
+            .map(|coordinates| coordinates.iter().map(Cell::from).collect());
+        cells.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<Coordinate>>, D::Error> {
+        let cells: Option<Vec<Cell>> = Option::deserialize(deserializer)?;
+        Ok(cells.map(|cells| cells.into_iter().map(Coordinate::from).collect()))
+    }
+}
+
+impl CheckReport {
+    /// complete without conflicts and matching the unique solution
+    pub fn is_solved(&self) -> bool {
+        self.complete
+            && self.conflicts.is_empty()
+            && self
+                .incorrect
+                .as_ref()
+                .map(|incorrect| incorrect.is_empty())
+                .unwrap_or(false)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+pub fn check<const N: usize, S: Solver>(solver: &S, grid: &Grid<N>) -> CheckReport {
+    let puzzle = givens(grid);
+    let solutions = solver.solutions(&puzzle, 2);
+    let givens = match solutions.len() {
+        0 => Uniqueness::None,
+        1 => Uniqueness::Unique,
+        _ => Uniqueness::Multiple,
+    };
+
+    let filled = filled_coordinates(grid);
+    let incorrect = match givens {
+        Uniqueness::Unique => Some(
+            filled
+                .iter()
+                .copied()
+                .filter(|coordinate| {
+                    grid.get_cell(*coordinate).value != solutions[0].get_cell(*coordinate).value
+                })
+                .collect(),
+        ),
+        _ => None,
+    };
+
+    CheckReport {
+        conflicts: conflicts(grid),
+        complete: filled.len() == N * N,
+        givens,
+        incorrect,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parse_line;
+    use crate::solver::Backtracking;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    #[test]
+    fn reports_conflicts_and_incorrect_values() {
+        let mut grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        // r1c3 is correct, a 3 in r1c4 is wrong and repeats the given in r1c2
+        grid.set_cell_value(Coordinate(0, 2), Some(3)).unwrap();
+        grid.set_cell_value(Coordinate(0, 3), Some(2)).unwrap();
+
+        let report = check(&Backtracking, &grid);
+        assert_eq!(report.conflicts, [[Coordinate(0, 1), Coordinate(0, 3)]]);
+        assert!(!report.complete);
+        assert_eq!(report.givens, Uniqueness::Unique);
+        assert_eq!(report.incorrect, Some(vec![Coordinate(0, 3)]));
+        assert!(!report.is_solved());
+
+        assert_eq!(
+            report.to_json(),
+            concat!(
+                r#"{"conflicts":[[{"row":1,"col":2},{"row":1,"col":4}]],"complete":false,"#,
+                r#""givens":"Unique","incorrect":[{"row":1,"col":4}]}"#
+            )
+        );
+        let parsed: CheckReport = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn solved_and_ambiguous_grids() {
+        let solved = check(&Backtracking, &parse_line::<9>(SOLUTION).unwrap());
+        assert!(solved.is_solved());
+        assert_eq!(solved.incorrect, Some(Vec::new()));
+
+        let empty = check(&Backtracking, &parse_line::<9>(&".".repeat(81)).unwrap());
+        assert_eq!(empty.givens, Uniqueness::Multiple);
+        assert_eq!(empty.incorrect, None);
+        assert!(!empty.is_solved());
+        assert_eq!(
+            empty.to_json(),
+            r#"{"conflicts":[],"complete":false,"givens":"Multiple","incorrect":null}"#
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use super::{conflicts, filled_coordinates, without};
+use crate::model::{Coordinate, Grid};
+use crate::solver::board::Board;
+use crate::solver::{grid_values, Backtracking};
+
+/// search nodes spent proving a reduced set of values has no solution,
+/// retried with the next budget when the search gives up
+const SEARCH_BUDGETS: [usize; 2] = [20_000, 200_000];
+
+/// Explanation of why a grid has no solution
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Contradiction {
+    /// filled cells whose values together leave no solution. Clearing any one of them
+    /// makes the rest solvable, unless showing it took more than the search budgets
+    pub cells: Vec<Coordinate>,
+    /// first empty cell left without candidates when propagating `cells`, if any
+    pub empty_cell: Option<Coordinate>,
+}
+
+///
+/// Find a small set of filled cells (givens and entries) that cannot all be kept.
+/// Returns `None` if `grid` has a solution.
+///
+/// Two values conflicting in a row, column or sub grid are reported directly;
+/// otherwise filled cells are dropped one at a time (row by row)
+/// as long as the remaining values can be shown to have no solution within a search budget.
+///
+pub fn find_contradiction<const N: usize>(grid: &Grid<N>) -> Option<Contradiction> {
+    find_contradiction_within(grid, usize::MAX).unwrap()
+}
+
+///
+/// Like `find_contradiction`, giving up after visiting `nodes` search nodes.
+/// `None` if the budget ran out before telling whether `grid` has a solution; running out
+/// while dropping cells keeps the cells not dropped so far.
+///
+pub fn find_contradiction_within<const N: usize>(
+    grid: &Grid<N>,
+    nodes: usize,
+) -> Option<Option<Contradiction>> {
+    let mut nodes = nodes;
+    if Backtracking::is_solvable_within(grid, &mut nodes)? {
+        return Some(None);
+    }
+
+    if let Some(conflict) = conflicts(grid).first() {
+        return Some(Some(Contradiction {
+            cells: conflict.to_vec(),
+            empty_cell: None,
+        }));
+    }
+
+    let mut reduced = grid.clone();
+    for coordinate in filled_coordinates(grid) {
+        if nodes == 0 {
+            break;
+        }
+        let candidate = without(&reduced, &[coordinate]);
+        let solvable = SEARCH_BUDGETS.iter().find_map(|budget| {
+            let budget = (*budget).min(nodes);
+            let mut left = budget;
+            let solvable = Backtracking::is_solvable_within(&candidate, &mut left);
+            nodes -= budget - left;
+            solvable
+        });
+        if solvable == Some(false) {
+            reduced = candidate;
+        }
+    }
+
+    Some(Some(Contradiction {
+        cells: filled_coordinates(&reduced),
+        empty_cell: propagate(&reduced),
+    }))
+}
+
+///
+/// Place naked and hidden singles until nothing changes,
+/// returning the first empty cell left without candidates.
+///
+fn propagate<const N: usize>(grid: &Grid<N>) -> Option<Coordinate> {
+    let mut board: Board<N> = Board::new(&grid_values(grid))?;
+    let units = board.units();
+    let coordinate = |i: usize| Coordinate((i / N) as u8, (i % N) as u8);
+
+    loop {
+        let mut changed = false;
+
+        for i in 0..N * N {
+            if board.cells[i].is_some() {
+                continue;
+            }
+            let candidates = board.candidates(i);
+            match candidates.count_ones() {
+                0 => return Some(coordinate(i)),
+                1 => {
+                    board.place(i, candidates.trailing_zeros() as u8);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        for unit in &units {
+            for value in 0..N as u8 {
+                if unit.iter().any(|i| board.cells[*i] == Some(value)) {
+                    continue;
+                }
+                let mut places = unit.iter().filter(|i| {
+                    board.cells[**i].is_none() && board.candidates(**i) & (1 << value) != 0
+                });
+                if let (Some(i), None) = (places.next(), places.next()) {
+                    board.place(*i, value);
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            return None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parse_line;
+    use crate::solver::Solver;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    /// r1c1 has no candidate: row 1 holds 1 to 8 and r4c1 a 9 outside their box, r9c9 is unrelated
+    const NO_SOLUTION: &str = concat!(
+        ".12345678",
+        ".........",
+        ".........",
+        "9........",
+        ".........",
+        ".........",
+        ".........",
+        ".........",
+        "........3",
+    );
+
+    #[test]
+    fn solvable_grids_have_no_contradiction() {
+        let grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        assert_eq!(find_contradiction(&grid), None);
+    }
+
+    #[test]
+    fn reports_conflicts_directly() {
+        let grid: Grid<9> =
+            parse_line(&format!("5{}", &PUZZLE[1..]).replacen('3', "5", 1)).unwrap();
+        let contradiction = find_contradiction(&grid).unwrap();
+        assert_eq!(contradiction.cells, [Coordinate(0, 0), Coordinate(0, 1)]);
+        assert_eq!(contradiction.empty_cell, None);
+    }
+
+    #[test]
+    fn finds_the_cells_leaving_no_candidate() {
+        let grid: Grid<9> = parse_line(NO_SOLUTION).unwrap();
+        assert!(conflicts(&grid).is_empty());
+
+        let contradiction = find_contradiction(&grid).unwrap();
+        let mut expected: Vec<Coordinate> = (1..9).map(|c| Coordinate(0, c)).collect();
+        expected.push(Coordinate(3, 0));
+        assert_eq!(contradiction.cells, expected);
+        assert_eq!(contradiction.empty_cell, Some(Coordinate(0, 0)));
+
+        // minimal: clearing any one of the cells leaves a solvable grid
+        let reduced = without(&grid, &[Coordinate(8, 8)]);
+        for coordinate in &contradiction.cells {
+            let cleared = without(&reduced, &[*coordinate]);
+            assert_eq!(
+                Backtracking.count_solutions(&cleared, 1),
+                1,
+                "{}",
+                coordinate
+            );
+        }
+    }
+
+    #[test]
+    fn budgeted_searches_give_up() {
+        let empty: Grid<9> = parse_line(&".".repeat(81)).unwrap();
+        assert_eq!(find_contradiction_within(&empty, 50), None);
+        assert_eq!(find_contradiction_within(&empty, 10_000), Some(None));
+
+        let grid: Grid<9> = parse_line(NO_SOLUTION).unwrap();
+        assert_eq!(
+            find_contradiction_within(&grid, 1_000_000),
+            Some(find_contradiction(&grid))
+        );
+        // out of nodes while dropping cells, the grid is still shown to have no solution
+        let contradiction = find_contradiction_within(&grid, 100).unwrap().unwrap();
+        assert!(contradiction.cells.len() >= 9);
+    }
+}
@@ -0,0 +1,10 @@
+use crate::model::{Difficulty, Grid};
+use crate::solver::logical::solve_logically;
+
+///
+/// Difficulty of the hardest technique needed to solve `grid` from its filled cells.
+/// Only meaningful for grids with a unique solution.
+///
+pub fn grade<const N: usize>(grid: &Grid<N>) -> Difficulty {
+    solve_logically(grid).difficulty()
+}
@@ -0,0 +1,176 @@
+use super::{filled_coordinates, givens, without};
+use crate::error::SudokuError;
+use crate::model::{Coordinate, Grid, Symmetry};
+use crate::solver::{Solver, Uniqueness};
+
+///
+/// Givens that can be removed one at a time while the puzzle keeps a unique solution.
+/// Player entries (editable values) are ignored.
+///
+pub fn redundant_givens<const N: usize, S: Solver>(
+    solver: &S,
+    grid: &Grid<N>,
+) -> Result<Vec<Coordinate>, SudokuError> {
+    let puzzle = givens(grid);
+    check_unique(solver, &puzzle)?;
+
+    let mut redundant: Vec<Coordinate> = Vec::new();
+    for coordinate in filled_coordinates(&puzzle) {
+        let reduced = without(&puzzle, &[coordinate]);
+        if solver.count_solutions(&reduced, 2) == 1 {
+            redundant.push(coordinate);
+        }
+    }
+
+    Ok(redundant)
+}
+
+/// whether no given can be removed without losing uniqueness
+pub fn is_minimal<const N: usize, S: Solver>(
+    solver: &S,
+    grid: &Grid<N>,
+) -> Result<bool, SudokuError> {
+    Ok(redundant_givens(solver, grid)?.is_empty())
+}
+
+///
+/// Remove redundant givens (row by row) until the puzzle is minimal.
+///
+/// Givens are removed together with the other cells of their `symmetry` orbit,
+/// so the result is minimal among puzzles keeping that symmetry.
+///
+pub fn minimize<const N: usize, S: Solver>(
+    solver: &S,
+    grid: &Grid<N>,
+    symmetry: Symmetry,
+) -> Result<Grid<N>, SudokuError> {
+    let mut puzzle = givens(grid);
+    check_unique(solver, &puzzle)?;
+
+    for coordinate in filled_coordinates(&puzzle) {
+        if puzzle.get_cell(coordinate).value.is_none() {
+            // already removed with its orbit
+            continue;
+        }
+
+        let orbit = symmetry.orbit(coordinate, N as u8);
+        let reduced = without(&puzzle, &orbit);
+        if solver.count_solutions(&reduced, 2) == 1 {
+            puzzle = reduced;
+        }
+    }
+
+    Ok(puzzle)
+}
+
+fn check_unique<const N: usize, S: Solver>(
+    solver: &S,
+    puzzle: &Grid<N>,
+) -> Result<(), SudokuError> {
+    match solver.uniqueness(puzzle) {
+        Uniqueness::Unique => Ok(()),
+        Uniqueness::None => Err(SudokuError::NoUniqueSolution(String::from(
+            "Givens have no solution",
+        ))),
+        Uniqueness::Multiple => Err(SudokuError::NoUniqueSolution(String::from(
+            "Givens have more than one solution",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+    use crate::solver::Backtracking;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    /// whether removing any single given loses uniqueness, checked directly with the solver
+    fn check_minimal(puzzle: &Grid<9>) {
+        assert_eq!(Backtracking.uniqueness(puzzle), Uniqueness::Unique);
+        for coordinate in filled_coordinates(puzzle) {
+            let reduced = without(puzzle, &[coordinate]);
+            assert_eq!(
+                Backtracking.count_solutions(&reduced, 2),
+                2,
+                "{}",
+                coordinate
+            );
+        }
+    }
+
+    #[test]
+    fn redundant_givens_keep_uniqueness() {
+        let grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        let redundant = redundant_givens(&Backtracking, &grid).unwrap();
+        assert!(!redundant.is_empty());
+        for coordinate in filled_coordinates(&grid) {
+            let reduced = without(&grid, &[coordinate]);
+            let unique = Backtracking.count_solutions(&reduced, 2) == 1;
+            assert_eq!(redundant.contains(&coordinate), unique, "{}", coordinate);
+        }
+        assert!(!is_minimal(&Backtracking, &grid).unwrap());
+    }
+
+    #[test]
+    fn every_cell_of_a_solution_is_redundant() {
+        let grid: Grid<9> = parse_line(SOLUTION).unwrap();
+        assert_eq!(redundant_givens(&Backtracking, &grid).unwrap().len(), 81);
+    }
+
+    #[test]
+    fn minimize_keeps_the_solution() {
+        let grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        let minimal = minimize(&Backtracking, &grid, Symmetry::None).unwrap();
+        check_minimal(&minimal);
+        assert!(is_minimal(&Backtracking, &minimal).unwrap());
+        let solution = Backtracking.solve(&minimal).unwrap();
+        assert_eq!(format_line(&solution).unwrap(), SOLUTION);
+        // only givens of the puzzle are kept
+        for coordinate in filled_coordinates(&minimal) {
+            assert_eq!(
+                minimal.get_cell(coordinate).value,
+                grid.get_cell(coordinate).value
+            );
+        }
+    }
+
+    #[test]
+    fn minimize_keeps_the_symmetry() {
+        let grid: Grid<9> = parse_line(SOLUTION).unwrap();
+        let minimal = minimize(&Backtracking, &grid, Symmetry::Rotational).unwrap();
+        assert_eq!(Backtracking.uniqueness(&minimal), Uniqueness::Unique);
+        for coordinate in filled_coordinates(&minimal) {
+            for cell in Symmetry::Rotational.orbit(coordinate, 9) {
+                assert!(minimal.get_cell(cell).value.is_some(), "{}", cell);
+            }
+        }
+    }
+
+    #[test]
+    fn entries_are_not_givens() {
+        let mut grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        grid.set_cell_value(Coordinate(0, 2), Some(3)).unwrap();
+        let minimal = minimize(&Backtracking, &grid, Symmetry::None).unwrap();
+        assert_eq!(minimal.get_cell(Coordinate(0, 2)).value, None);
+    }
+
+    #[test]
+    fn rejects_puzzles_without_unique_solution() {
+        let several: Grid<9> = parse_line(&format!("{}{}", &PUZZLE[..80], ".")).unwrap();
+        let several = without(&several, &[Coordinate(0, 0), Coordinate(0, 1)]);
+        assert!(matches!(
+            redundant_givens(&Backtracking, &several),
+            Err(SudokuError::NoUniqueSolution(_))
+        ));
+        let none: Grid<9> = parse_line(&format!("55{}", &PUZZLE[2..])).unwrap();
+        assert!(matches!(
+            minimize(&Backtracking, &none, Symmetry::None),
+            Err(SudokuError::NoUniqueSolution(_))
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+mod ambiguity;
+mod check;
+mod contradiction;
+mod difficulty;
+mod minimality;
+
+pub use ambiguity::{find_ambiguity, find_ambiguity_within, Ambiguity};
+pub use check::{check, CheckReport};
+pub use contradiction::{find_contradiction, find_contradiction_within, Contradiction};
+pub use difficulty::grade;
+pub use minimality::{is_minimal, minimize, redundant_givens};
+
+use crate::model::{Coordinate, Grid};
+
+/// copy of `grid` keeping only its givens (non-editable values)
+pub fn givens<const N: usize>(grid: &Grid<N>) -> Grid<N> {
+    let mut result = grid.clone();
+    for coordinate in filled_coordinates(grid) {
+        if grid.get_cell(coordinate).editable {
+            result.set_cell_value(coordinate, None).unwrap();
+        }
+    }
+    result
+}
+
+/// pairs of filled cells with the same value in a row, column or sub grid
+pub fn conflicts<const N: usize>(grid: &Grid<N>) -> Vec<[Coordinate; 2]> {
+    let filled = filled_coordinates(grid);
+    let mut conflicts: Vec<[Coordinate; 2]> = Vec::new();
+    for (i, coor1) in filled.iter().enumerate() {
+        for coor2 in &filled[i + 1..] {
+            if grid.get_cell(*coor1).value == grid.get_cell(*coor2).value
+                && !grid.get_cells_relation(*coor1, *coor2).is_empty()
+            {
+                conflicts.push([*coor1, *coor2]);
+            }
+        }
+    }
+    conflicts
+}
+
+/// coordinates of cells with a value, row by row
+fn filled_coordinates<const N: usize>(grid: &Grid<N>) -> Vec<Coordinate> {
+    grid.rows
+        .iter()
+        .flat_map(|row| row.cells.iter())
+        .filter(|cell| cell.value.is_some())
+        .map(|cell| cell.coordinate)
+        .collect()
+}
+
+/// copy of `grid` with cells at `coordinates` cleared
+fn without<const N: usize>(grid: &Grid<N>, coordinates: &[Coordinate]) -> Grid<N> {
+    let mut result = grid.clone();
+    for coordinate in coordinates {
+        result.set_cell_value(*coordinate, None).unwrap();
+        result.set_cell_editable(*coordinate, true).unwrap();
+    }
+    result
+}
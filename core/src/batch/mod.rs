@@ -0,0 +1,264 @@
+use crate::analysis::grade;
+use crate::model::{Difficulty, Grid};
+use crate::solver::{Solver, Uniqueness};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Result of solving one grid of a batch
+#[derive(Clone)]
+pub struct Solved<const N: usize> {
+    pub solution: Option<Grid<N>>, // first solution found, if any
+    pub uniqueness: Uniqueness,
+}
+
+impl<const N: usize> Solved<N> {
+    pub fn new<S: Solver>(solver: &S, grid: &Grid<N>) -> Solved<N> {
+        let mut solutions = solver.solutions(grid, 2);
+        let uniqueness = match solutions.len() {
+            0 => Uniqueness::None,
+            1 => Uniqueness::Unique,
+            _ => Uniqueness::Multiple,
+        };
+        solutions.truncate(1);
+        Solved {
+            solution: solutions.pop(),
+            uniqueness,
+        }
+    }
+}
+
+/// Result of grading one grid of a batch
+#[derive(Clone)]
+pub struct Graded<const N: usize> {
+    pub solved: Solved<N>,
+    pub difficulty: Option<Difficulty>, // only graded with a unique solution
+}
+
+impl<const N: usize> Graded<N> {
+    pub fn new<S: Solver>(solver: &S, grid: &Grid<N>) -> Graded<N> {
+        let solved = Solved::new(solver, grid);
+        let difficulty = match solved.uniqueness {
+            Uniqueness::Unique => Some(grade(grid)),
+            _ => None,
+        };
+        Graded { solved, difficulty }
+    }
+}
+
+/// number of threads to use for a batch by default
+pub fn available_threads() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+///
+/// Solve `grids` on `threads` worker threads.
+/// Results are in the same order as `grids`.
+///
+pub fn solve_batch<const N: usize, S: Solver + Sync>(
+    solver: &S,
+    grids: &[Grid<N>],
+    threads: usize,
+) -> Vec<Solved<N>> {
+    run_batch(grids, threads, |grid| Solved::new(solver, grid))
+}
+
+///
+/// Solve and grade `grids` on `threads` worker threads, see `grade`.
+/// Results are in the same order as `grids`.
+///
+pub fn grade_batch<const N: usize, S: Solver + Sync>(
+    solver: &S,
+    grids: &[Grid<N>],
+    threads: usize,
+) -> Vec<Graded<N>> {
+    run_batch(grids, threads, |grid| Graded::new(solver, grid))
+}
+
+/// `task` applied to each of `grids` on `threads` worker threads, in the order of `grids`
+fn run_batch<const N: usize, T, F>(grids: &[Grid<N>], threads: usize, task: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&Grid<N>) -> T + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let threads = threads.clamp(1, grids.len().max(1));
+    let mut results: Vec<Option<T>> = grids.iter().map(|_| None).collect();
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done: Vec<(usize, T)> = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= grids.len() {
+                            break;
+                        }
+                        done.push((i, task(&grids[i])));
+                    }
+                    done
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            for (i, result) in worker.join().unwrap() {
+                results[i] = Some(result);
+            }
+        }
+    });
+
+    results.into_iter().map(|result| result.unwrap()).collect()
+}
+
+/// Counts of batch results by uniqueness
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchSummary {
+    pub puzzles: usize,
+    pub unique: usize,
+    pub unsolvable: usize,
+    pub multiple: usize,
+    pub errors: usize, // puzzles that could not be read
+    pub elapsed: Duration,
+}
+
+impl BatchSummary {
+    pub fn add<const N: usize>(&mut self, solved: &Solved<N>) {
+        self.puzzles += 1;
+        match solved.uniqueness {
+            Uniqueness::None => self.unsolvable += 1,
+            Uniqueness::Unique => self.unique += 1,
+            Uniqueness::Multiple => self.multiple += 1,
+        }
+    }
+
+    pub fn add_error(&mut self) {
+        self.puzzles += 1;
+        self.errors += 1;
+    }
+
+    pub fn puzzles_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.puzzles as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+    use crate::solver::Backtracking;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    fn grids() -> Vec<Grid<9>> {
+        let lines = [
+            String::from(PUZZLE),
+            format!("55{}", &PUZZLE[2..]),
+            ".".repeat(81),
+            String::from(SOLUTION),
+        ];
+        lines
+            .iter()
+            .cycle()
+            .take(30)
+            .map(|line| parse_line(line).unwrap())
+            .collect()
+    }
+
+    fn solved_line(solved: &Solved<9>) -> Option<String> {
+        solved
+            .solution
+            .as_ref()
+            .map(|solution| format_line(solution).unwrap())
+    }
+
+    fn summary(results: &[Solved<9>]) -> BatchSummary {
+        let mut summary = BatchSummary::default();
+        for solved in results {
+            summary.add(solved);
+        }
+        summary
+    }
+
+    #[test]
+    fn results_keep_the_order_of_the_grids() {
+        let grids = grids();
+        for threads in [1, 3, 64] {
+            let results = solve_batch(&Backtracking, &grids, threads);
+            assert_eq!(results.len(), grids.len());
+            for (grid, solved) in grids.iter().zip(&results) {
+                let expected = Solved::new(&Backtracking, grid);
+                assert_eq!(solved.uniqueness, expected.uniqueness);
+                assert_eq!(solved_line(solved), solved_line(&expected));
+            }
+            assert_eq!(
+                solved_line(&results[0]),
+                Some(String::from(SOLUTION)),
+                "{} threads",
+                threads
+            );
+        }
+    }
+
+    #[test]
+    fn summary_counts_by_uniqueness() {
+        let results = solve_batch(&Backtracking, &grids(), 4);
+        let summary = summary(&results);
+        assert_eq!(summary.puzzles, 30);
+        assert_eq!(summary.unique, 15);
+        assert_eq!(summary.unsolvable, 8);
+        assert_eq!(summary.multiple, 7);
+        assert_eq!(summary.puzzles_per_second(), 0.0);
+
+        let timed = BatchSummary {
+            elapsed: Duration::from_millis(500),
+            ..summary
+        };
+        assert_eq!(timed.puzzles_per_second(), 60.0);
+    }
+
+    #[test]
+    fn grades_puzzles_with_a_unique_solution() {
+        let grids = grids();
+        let results = grade_batch(&Backtracking, &grids, 3);
+        assert_eq!(results.len(), grids.len());
+        for (grid, graded) in grids.iter().zip(&results) {
+            assert_eq!(
+                graded.solved.uniqueness,
+                Solved::new(&Backtracking, grid).uniqueness
+            );
+            match graded.solved.uniqueness {
+                Uniqueness::Unique => assert_eq!(graded.difficulty, Some(grade(grid))),
+                _ => assert_eq!(graded.difficulty, None),
+            }
+        }
+        assert_eq!(results[0].difficulty, Some(Difficulty::Easy));
+    }
+
+    #[test]
+    fn errors_count_as_puzzles() {
+        let mut summary = summary(&solve_batch(&Backtracking, &grids()[..4], 2));
+        summary.add_error();
+        assert_eq!(summary.puzzles, 5);
+        assert_eq!(summary.errors, 1);
+        assert_eq!(summary.unique + summary.unsolvable + summary.multiple, 4);
+    }
+
+    #[test]
+    fn empty_batches() {
+        assert!(solve_batch::<9, _>(&Backtracking, &[], 0).is_empty());
+        assert!(grade_batch::<9, _>(&Backtracking, &[], 0).is_empty());
+        assert!(available_threads() >= 1);
+    }
+}
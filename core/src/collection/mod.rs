@@ -0,0 +1,239 @@
+mod puzzle;
+mod reader;
+mod writer;
+
+pub use puzzle::{Puzzle, PuzzleMetadata};
+pub use reader::PuzzleReader;
+pub use writer::PuzzleWriter;
+
+use crate::error::SudokuError;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CollectionFormat {
+    Lines,     // one puzzle line per line, optionally followed by its id
+    JsonLines, // one json object per line: `{"puzzle": "53..7...", "id": "42", ...}`
+}
+
+impl CollectionFormat {
+    /// `.jsonl` / `.ndjson` files are json lines, anything else is read as lines
+    pub fn from_path(path: &str) -> CollectionFormat {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("jsonl") | Some("ndjson") => CollectionFormat::JsonLines,
+            _ => CollectionFormat::Lines,
+        }
+    }
+}
+
+/// json lines record, puzzle in the single line format
+#[derive(Serialize, Deserialize)]
+struct PuzzleRecord {
+    puzzle: String,
+    #[serde(flatten)]
+    metadata: PuzzleMetadata,
+}
+
+pub struct PuzzleCollection<const N: usize> {
+    pub puzzles: Vec<Puzzle<N>>,
+}
+
+impl<const N: usize> PuzzleCollection<N> {
+    pub fn new() -> PuzzleCollection<N> {
+        PuzzleCollection {
+            puzzles: Vec::new(),
+        }
+    }
+
+    /// stream puzzles of the file at `path` without loading the whole file
+    pub fn stream(path: &str) -> Result<PuzzleReader<BufReader<File>, N>, SudokuError> {
+        let file = File::open(path)?;
+        Ok(PuzzleReader::new(
+            BufReader::new(file),
+            CollectionFormat::from_path(path),
+        ))
+    }
+
+    pub fn from_reader<R: BufRead>(
+        reader: R,
+        format: CollectionFormat,
+    ) -> Result<PuzzleCollection<N>, SudokuError> {
+        PuzzleReader::new(reader, format).collect()
+    }
+
+    pub fn read(path: &str) -> Result<PuzzleCollection<N>, SudokuError> {
+        Self::stream(path)?.collect()
+    }
+
+    pub fn write(&self, path: &str) -> Result<(), SudokuError> {
+        let file = File::create(path)?;
+        let mut writer = PuzzleWriter::new(BufWriter::new(file), CollectionFormat::from_path(path));
+        for puzzle in &self.puzzles {
+            writer.write(puzzle)?;
+        }
+        writer.flush()
+    }
+
+    pub fn push(&mut self, puzzle: Puzzle<N>) {
+        self.puzzles.push(puzzle);
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Puzzle<N>> {
+        self.puzzles.iter()
+    }
+}
+
+impl<const N: usize> Default for PuzzleCollection<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FromIterator<Puzzle<N>> for PuzzleCollection<N> {
+    fn from_iter<I: IntoIterator<Item = Puzzle<N>>>(iter: I) -> Self {
+        PuzzleCollection {
+            puzzles: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<const N: usize> IntoIterator for PuzzleCollection<N> {
+    type Item = Puzzle<N>;
+    type IntoIter = std::vec::IntoIter<Puzzle<N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.puzzles.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+    use std::io::Cursor;
+
+    const PUZZLE: &str = "12............43";
+
+    fn lines(collection: &PuzzleCollection<4>) -> Vec<String> {
+        collection
+            .iter()
+            .map(|puzzle| format_line(&puzzle.grid).unwrap())
+            .collect()
+    }
+
+    fn write(collection: &PuzzleCollection<4>, format: CollectionFormat) -> String {
+        let mut writer = PuzzleWriter::new(Vec::new(), format);
+        for puzzle in collection.iter() {
+            writer.write(puzzle).unwrap();
+        }
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(
+            CollectionFormat::from_path("a/puzzles.JSONL"),
+            CollectionFormat::JsonLines
+        );
+        assert_eq!(
+            CollectionFormat::from_path("puzzles.ndjson"),
+            CollectionFormat::JsonLines
+        );
+        assert_eq!(
+            CollectionFormat::from_path("puzzles.txt"),
+            CollectionFormat::Lines
+        );
+    }
+
+    #[test]
+    fn reads_lines_with_ids() {
+        let content = format!(
+            "# collection\n\n{} 42\n{},a-7\n{}#x\n{}\n",
+            PUZZLE, PUZZLE, PUZZLE, PUZZLE
+        );
+        let collection: PuzzleCollection<4> =
+            PuzzleCollection::from_reader(Cursor::new(content), CollectionFormat::Lines).unwrap();
+        assert_eq!(lines(&collection), [PUZZLE; 4]);
+        let ids: Vec<Option<&str>> = collection
+            .iter()
+            .map(|puzzle| puzzle.metadata.id.as_deref())
+            .collect();
+        assert_eq!(ids, [Some("42"), Some("a-7"), Some("x"), None]);
+    }
+
+    #[test]
+    fn lines_round_trip() {
+        let mut collection = PuzzleCollection::new();
+        let mut puzzle = Puzzle::new(parse_line(PUZZLE).unwrap());
+        puzzle.metadata.id = Some(String::from("1"));
+        puzzle.metadata.title = Some(String::from("dropped"));
+        collection.push(puzzle);
+        collection.push(Puzzle::new(parse_line("...4............").unwrap()));
+
+        let content = write(&collection, CollectionFormat::Lines);
+        assert_eq!(content, "12............43 1\n...4............\n");
+        let read: PuzzleCollection<4> =
+            PuzzleCollection::from_reader(Cursor::new(content), CollectionFormat::Lines).unwrap();
+        assert_eq!(lines(&read), lines(&collection));
+        assert_eq!(read.puzzles[0].metadata.id.as_deref(), Some("1"));
+        assert_eq!(read.puzzles[0].metadata.title, None);
+    }
+
+    #[test]
+    fn json_lines_round_trip() {
+        let mut puzzle = Puzzle::new(parse_line(PUZZLE).unwrap());
+        puzzle.metadata = PuzzleMetadata {
+            id: Some(String::from("7")),
+            title: Some(String::from("Tiny")),
+            difficulty: Some(String::from("easy")),
+            ..PuzzleMetadata::default()
+        };
+        let collection: PuzzleCollection<4> = vec![puzzle].into_iter().collect();
+
+        let content = write(&collection, CollectionFormat::JsonLines);
+        assert_eq!(
+            content,
+            concat!(
+                "{\"puzzle\":\"12............43\",",
+                "\"id\":\"7\",\"title\":\"Tiny\",\"difficulty\":\"easy\"}\n"
+            )
+        );
+        let read: PuzzleCollection<4> =
+            PuzzleCollection::from_reader(Cursor::new(content), CollectionFormat::JsonLines)
+                .unwrap();
+        assert_eq!(lines(&read), [PUZZLE]);
+        assert_eq!(read.puzzles[0].metadata, collection.puzzles[0].metadata);
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let content = format!("{}\n\n{}x\n", PUZZLE, PUZZLE);
+        let mut reader: PuzzleReader<_, 4> =
+            PuzzleReader::new(Cursor::new(content), CollectionFormat::Lines);
+        assert!(reader.next().unwrap().is_ok());
+        let error = reader.next().unwrap().err().unwrap();
+        assert!(error.to_string().starts_with("Line 3: "), "{}", error);
+        assert!(reader.next().is_none());
+
+        let mut reader: PuzzleReader<_, 4> =
+            PuzzleReader::new(Cursor::new("{\"id\": 1}"), CollectionFormat::JsonLines);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(SudokuError::InvalidFormat(_)))
+        ));
+    }
+}
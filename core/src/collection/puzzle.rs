@@ -0,0 +1,36 @@
+use crate::model::Grid;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct PuzzleMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl PuzzleMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == PuzzleMetadata::default()
+    }
+}
+
+pub struct Puzzle<const N: usize> {
+    pub grid: Grid<N>,
+    pub metadata: PuzzleMetadata,
+}
+
+impl<const N: usize> Puzzle<N> {
+    pub fn new(grid: Grid<N>) -> Puzzle<N> {
+        Puzzle {
+            grid,
+            metadata: PuzzleMetadata::default(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::{CollectionFormat, Puzzle, PuzzleMetadata, PuzzleRecord};
+use crate::error::SudokuError;
+use crate::format::parse_line;
+use std::io::{BufRead, Lines};
+
+///
+/// Streams puzzles of a collection one line at a time,
+/// blank lines and lines starting with `#` are skipped.
+///
+pub struct PuzzleReader<R: BufRead, const N: usize> {
+    lines: Lines<R>,
+    format: CollectionFormat,
+    line_number: usize,
+}
+
+impl<R: BufRead, const N: usize> PuzzleReader<R, N> {
+    pub fn new(reader: R, format: CollectionFormat) -> PuzzleReader<R, N> {
+        PuzzleReader {
+            lines: reader.lines(),
+            format,
+            line_number: 0,
+        }
+    }
+
+    fn parse(&self, line: &str) -> Result<Puzzle<N>, SudokuError> {
+        match self.format {
+            CollectionFormat::Lines => {
+                // puzzle, optionally followed by an id: `53..7....6..195... #42`
+                let (puzzle, rest) = match line.find(|c: char| is_separator(c)) {
+                    Some(i) => line.split_at(i),
+                    None => (line, ""),
+                };
+                let id = rest
+                    .trim_start_matches(|c: char| is_separator(c))
+                    .trim_end();
+
+                Ok(Puzzle {
+                    grid: parse_line(puzzle)?,
+                    metadata: PuzzleMetadata {
+                        id: if id.is_empty() {
+                            None
+                        } else {
+                            Some(String::from(id))
+                        },
+                        ..PuzzleMetadata::default()
+                    },
+                })
+            }
+            CollectionFormat::JsonLines => {
+                let record: PuzzleRecord = serde_json::from_str(line).map_err(|error| {
+                    SudokuError::InvalidFormat(format!("Invalid puzzle record: {}", error))
+                })?;
+
+                Ok(Puzzle {
+                    grid: parse_line(&record.puzzle)?,
+                    metadata: record.metadata,
+                })
+            }
+        }
+    }
+}
+
+impl<R: BufRead, const N: usize> Iterator for PuzzleReader<R, N> {
+    type Item = Result<Puzzle<N>, SudokuError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            self.line_number += 1;
+
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => return Some(Err(SudokuError::from(error).at_line(self.line_number))),
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            return Some(
+                self.parse(line)
+                    .map_err(|error| error.at_line(self.line_number)),
+            );
+        }
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ',' | ';' | '#')
+}
@@ -0,0 +1,54 @@
+use super::{CollectionFormat, Puzzle, PuzzleRecord};
+use crate::error::SudokuError;
+use crate::format::format_line;
+use std::io::Write;
+
+/// Writes puzzles of a collection one line at a time, see `PuzzleReader`
+pub struct PuzzleWriter<W: Write> {
+    writer: W,
+    format: CollectionFormat,
+}
+
+impl<W: Write> PuzzleWriter<W> {
+    pub fn new(writer: W, format: CollectionFormat) -> PuzzleWriter<W> {
+        PuzzleWriter { writer, format }
+    }
+
+    ///
+    /// Write a puzzle as one line.
+    /// With `CollectionFormat::Lines` only the id is kept from the metadata.
+    ///
+    pub fn write<const N: usize>(&mut self, puzzle: &Puzzle<N>) -> Result<(), SudokuError> {
+        let line = match self.format {
+            CollectionFormat::Lines => {
+                let mut line = format_line(&puzzle.grid)?;
+                if let Some(id) = &puzzle.metadata.id {
+                    line.push(' ');
+                    line.push_str(id);
+                }
+                line
+            }
+            CollectionFormat::JsonLines => {
+                let record = PuzzleRecord {
+                    puzzle: format_line(&puzzle.grid)?,
+                    metadata: puzzle.metadata.clone(),
+                };
+                serde_json::to_string(&record).map_err(|error| {
+                    SudokuError::InvalidFormat(format!("Could not serialize puzzle: {}", error))
+                })?
+            }
+        };
+
+        writeln!(self.writer, "{}", line)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), SudokuError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
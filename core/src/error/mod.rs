@@ -2,4 +2,65 @@
 pub enum SudokuError {
     InvalidCellValue(String),
     CellCoordinateOutOfBound(String),
+    UnsupportedGridSize(String),
+    InvalidLength(String),
+    InvalidCharacter(String),
+    InvalidFormat(String),
+    Io(String),
+    NoUniqueSolution(String),
+    GenerationFailed(String),
 }
+
+impl std::fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SudokuError::InvalidCellValue(message)
+            | SudokuError::CellCoordinateOutOfBound(message)
+            | SudokuError::UnsupportedGridSize(message)
+            | SudokuError::InvalidLength(message)
+            | SudokuError::InvalidCharacter(message)
+            | SudokuError::InvalidFormat(message)
+            | SudokuError::Io(message)
+            | SudokuError::NoUniqueSolution(message)
+            | SudokuError::GenerationFailed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl SudokuError {
+    /// prefix message with the (1-based) line of the input the error occurred at
+    pub fn at_line(self, line: usize) -> Self {
+        let prefix = |message: String| format!("Line {}: {}", line, message);
+        match self {
+            SudokuError::InvalidCellValue(message) => {
+                SudokuError::InvalidCellValue(prefix(message))
+            }
+            SudokuError::CellCoordinateOutOfBound(message) => {
+                SudokuError::CellCoordinateOutOfBound(prefix(message))
+            }
+            SudokuError::UnsupportedGridSize(message) => {
+                SudokuError::UnsupportedGridSize(prefix(message))
+            }
+            SudokuError::InvalidLength(message) => SudokuError::InvalidLength(prefix(message)),
+            SudokuError::InvalidCharacter(message) => {
+                SudokuError::InvalidCharacter(prefix(message))
+            }
+            SudokuError::InvalidFormat(message) => SudokuError::InvalidFormat(prefix(message)),
+            SudokuError::Io(message) => SudokuError::Io(prefix(message)),
+            SudokuError::NoUniqueSolution(message) => {
+                SudokuError::NoUniqueSolution(prefix(message))
+            }
+            SudokuError::GenerationFailed(message) => {
+                SudokuError::GenerationFailed(prefix(message))
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for SudokuError {
+    fn from(error: std::io::Error) -> Self {
+        SudokuError::Io(error.to_string())
+    }
+}
+
+impl std::error::Error for SudokuError {}
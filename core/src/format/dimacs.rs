@@ -0,0 +1,10 @@
+use super::check_size;
+use crate::error::SudokuError;
+use crate::model::Grid;
+use crate::solver::Cnf;
+
+/// DIMACS CNF of the grid, see `Cnf` for the variable numbering
+pub fn format_dimacs<const N: usize>(grid: &Grid<N>) -> Result<String, SudokuError> {
+    check_size::<N>()?;
+    Ok(Cnf::from_grid(grid).to_dimacs())
+}
@@ -0,0 +1,233 @@
+use super::fpuzzles::is_compressed_description;
+use super::{
+    decode_share_code, encode_share_code, format_fpuzzles, format_line, format_sdk, format_sdm,
+    format_ss, is_share_code, parse_fpuzzles, parse_sdk, parse_sdm, parse_ss, FPuzzleInfo,
+};
+use crate::error::SudokuError;
+use crate::model::Grid;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FileFormat {
+    Json,         // serialized `Grid`
+    Line,         // one puzzle per line, see `parse_line`
+    SimpleSudoku, // .ss, see `parse_ss`
+    SadMan,       // .sdk, see `parse_sdk`
+    SadManMulti,  // .sdm, see `parse_sdm`
+    FPuzzles,     // f-puzzles / SudokuPad description, see `parse_fpuzzles`
+    ShareCode,    // see `encode_share_code`
+}
+
+impl FileFormat {
+    pub fn from_extension(path: &str) -> Option<FileFormat> {
+        let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(FileFormat::Json),
+            "ss" => Some(FileFormat::SimpleSudoku),
+            "sdk" => Some(FileFormat::SadMan),
+            "sdm" => Some(FileFormat::SadManMulti),
+            _ => None,
+        }
+    }
+
+    /// guess format from file content, used when extension is unknown
+    pub fn sniff(content: &str) -> FileFormat {
+        let trimmed = content.trim_start();
+        if trimmed.starts_with('{') {
+            if content.contains("\"size\"") && !content.contains("\"rows\"") {
+                return FileFormat::FPuzzles;
+            }
+            return FileFormat::Json;
+        }
+        if is_share_code(content) {
+            return FileFormat::ShareCode;
+        }
+        if is_compressed_description(content) {
+            return FileFormat::FPuzzles;
+        }
+        if content.contains('|') {
+            return FileFormat::SimpleSudoku;
+        }
+        if content.lines().any(|line| line.trim() == "[Puzzle]") {
+            return FileFormat::SadMan;
+        }
+
+        let lines: Vec<&str> = content
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        let is_square_block =
+            lines.len() > 1 && lines.iter().all(|line| line.chars().count() == lines.len());
+
+        if is_square_block {
+            FileFormat::SadMan
+        } else {
+            FileFormat::Line
+        }
+    }
+
+    /// format from extension of `path`, otherwise sniffed from `content`
+    pub fn detect(path: &str, content: &str) -> FileFormat {
+        FileFormat::from_extension(path).unwrap_or_else(|| FileFormat::sniff(content))
+    }
+
+    /// whether this format can hold more than one puzzle
+    pub fn is_multi(&self) -> bool {
+        matches!(self, FileFormat::Line | FileFormat::SadManMulti)
+    }
+
+    pub fn parse<const N: usize>(&self, content: &str) -> Result<Vec<Grid<N>>, SudokuError> {
+        match self {
+            FileFormat::Json => match serde_json::from_str(content) {
+                Ok(grid) => Ok(vec![grid]),
+                Err(error) => Err(SudokuError::InvalidFormat(format!(
+                    "Invalid grid json: {}",
+                    error
+                ))),
+            },
+            FileFormat::Line | FileFormat::SadManMulti => parse_sdm(content),
+            FileFormat::SimpleSudoku => Ok(vec![parse_ss(content)?]),
+            FileFormat::SadMan => Ok(vec![parse_sdk(content)?]),
+            FileFormat::FPuzzles => {
+                let puzzle = parse_fpuzzles(content)?;
+                if !puzzle.unsupported.is_empty() {
+                    return Err(SudokuError::InvalidFormat(format!(
+                        "Unsupported constraints: {}",
+                        puzzle.unsupported.join(", ")
+                    )));
+                }
+                Ok(vec![puzzle.grid])
+            }
+            FileFormat::ShareCode => Ok(vec![decode_share_code(content)?.grid]),
+        }
+    }
+
+    pub fn format<const N: usize>(&self, grids: &[Grid<N>]) -> Result<String, SudokuError> {
+        if !self.is_multi() && grids.len() != 1 {
+            return Err(SudokuError::InvalidFormat(format!(
+                "{:?} format holds exactly one puzzle, got {}",
+                self,
+                grids.len()
+            )));
+        }
+
+        match self {
+            FileFormat::Json => Ok(grids[0].to_json()),
+            FileFormat::Line => {
+                let mut content = String::new();
+                for grid in grids {
+                    content.push_str(&format_line(grid)?);
+                    content.push('\n');
+                }
+                Ok(content)
+            }
+            FileFormat::SimpleSudoku => format_ss(&grids[0]),
+            FileFormat::SadMan => format_sdk(&grids[0]),
+            FileFormat::SadManMulti => format_sdm(grids),
+            FileFormat::FPuzzles => format_fpuzzles(&grids[0], &FPuzzleInfo::default(), false),
+            FileFormat::ShareCode => encode_share_code(&grids[0], None),
+        }
+    }
+}
+
+impl std::fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parse_line;
+
+    const PUZZLE: &str = "12............43";
+    const FORMATS: [FileFormat; 7] = [
+        FileFormat::Json,
+        FileFormat::Line,
+        FileFormat::SimpleSudoku,
+        FileFormat::SadMan,
+        FileFormat::SadManMulti,
+        FileFormat::FPuzzles,
+        FileFormat::ShareCode,
+    ];
+
+    #[test]
+    fn formats_from_extensions() {
+        assert_eq!(FileFormat::from_extension("a.json"), Some(FileFormat::Json));
+        assert_eq!(
+            FileFormat::from_extension("dir/A.SS"),
+            Some(FileFormat::SimpleSudoku)
+        );
+        assert_eq!(
+            FileFormat::from_extension("a.sdk"),
+            Some(FileFormat::SadMan)
+        );
+        assert_eq!(
+            FileFormat::from_extension("a.sdm"),
+            Some(FileFormat::SadManMulti)
+        );
+        assert_eq!(FileFormat::from_extension("a.txt"), None);
+        assert_eq!(FileFormat::from_extension("sdk"), None);
+    }
+
+    #[test]
+    fn sniffs_formatted_puzzles() {
+        let grids: Vec<Grid<4>> = vec![parse_line(PUZZLE).unwrap()];
+        for format in FORMATS {
+            let content = format.format(&grids).unwrap();
+            let sniffed = FileFormat::sniff(&content);
+            // a single sdm line reads as a puzzle line
+            match format {
+                FileFormat::SadManMulti => assert_eq!(sniffed, FileFormat::Line),
+                _ => assert_eq!(sniffed, format, "{}", content),
+            }
+        }
+        assert_eq!(
+            FileFormat::sniff("# comment\n12..\n....\n....\n..43\n"),
+            FileFormat::SadMan
+        );
+        assert_eq!(
+            FileFormat::sniff(&format!("{0}\n{0}\n", PUZZLE)),
+            FileFormat::Line
+        );
+    }
+
+    #[test]
+    fn extensions_win_over_content() {
+        assert_eq!(FileFormat::detect("a.sdm", PUZZLE), FileFormat::SadManMulti);
+        assert_eq!(
+            FileFormat::detect("a.txt", "12|..\n"),
+            FileFormat::SimpleSudoku
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let grids: Vec<Grid<4>> = vec![parse_line(PUZZLE).unwrap()];
+        for format in FORMATS {
+            let content = format.format(&grids).unwrap();
+            let parsed: Vec<Grid<4>> = format.parse(&content).unwrap();
+            assert_eq!(parsed.len(), 1, "{}", format);
+            assert_eq!(format_line(&parsed[0]).unwrap(), PUZZLE, "{}", format);
+        }
+    }
+
+    #[test]
+    fn single_puzzle_formats_reject_several() {
+        let grids: Vec<Grid<4>> = vec![parse_line(PUZZLE).unwrap(); 2];
+        for format in FORMATS {
+            assert_eq!(
+                format.format(&grids).is_ok(),
+                format.is_multi(),
+                "{}",
+                format
+            );
+        }
+        assert_eq!(
+            FileFormat::Line.format(&grids).unwrap(),
+            format!("{0}\n{0}\n", PUZZLE)
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use super::check_size;
+use super::lz_string::{compress_to_base64, decompress_from_base64};
+use crate::error::SudokuError;
+use crate::model::{Cell, Coordinate, Grid};
+use serde_json::{json, Map, Value};
+
+/// top level keys of a puzzle description that are mapped onto `FPuzzle`
+const SUPPORTED_KEYS: [&str; 6] = ["size", "grid", "title", "author", "ruleset", "solution"];
+
+#[derive(Debug, Clone, Default)]
+pub struct FPuzzleInfo {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub ruleset: Option<String>,
+}
+
+/// puzzle imported from an f-puzzles / SudokuPad json description
+pub struct FPuzzle<const N: usize> {
+    pub grid: Grid<N>,
+    pub info: FPuzzleInfo,
+    /// constraints of the description that could not be mapped onto `grid`, i.e `killercage (2)`
+    pub unsupported: Vec<String>,
+}
+
+///
+/// Parse an f-puzzles / SudokuPad puzzle description.
+///
+/// `content` can be the raw json, its LZ-String base64 compressed form,
+/// or a puzzle url carrying the compressed form (`?load=...`, `/fpuzzles...`).
+///
+/// Givens become non-editable cells, other values are kept as player entries.
+/// Variant constraints (cages, lines, irregular regions...) are listed in `unsupported`.
+///
+pub fn parse_fpuzzles<const N: usize>(content: &str) -> Result<FPuzzle<N>, SudokuError> {
+    check_size::<N>()?;
+
+    let json = decode(content)?;
+    let description: Map<String, Value> = match serde_json::from_str(&json) {
+        Ok(Value::Object(description)) => description,
+        Ok(_) => {
+            return Err(SudokuError::InvalidFormat(String::from(
+                "Puzzle description must be a json object",
+            )))
+        }
+        Err(error) => {
+            return Err(SudokuError::InvalidFormat(format!(
+                "Invalid puzzle description json: {}",
+                error
+            )))
+        }
+    };
+
+    let size = description.get("size").and_then(Value::as_u64);
+    if size != Some(N as u64) {
+        return Err(SudokuError::UnsupportedGridSize(format!(
+            "Puzzle description has size {:?}, expected {}",
+            size, N
+        )));
+    }
+
+    let rows = description
+        .get("grid")
+        .and_then(Value::as_array)
+        .filter(|rows| rows.len() == N)
+        .ok_or_else(|| {
+            SudokuError::InvalidLength(format!("Puzzle description grid must have {} rows", N))
+        })?;
+
+    let mut grid: Grid<N> = Grid::new();
+    let mut unsupported: Vec<String> = Vec::new();
+    let mut irregular_regions = false;
+    let mut pencil_marks = 0;
+
+    for (x, row) in rows.iter().enumerate() {
+        let cells = row
+            .as_array()
+            .filter(|cells| cells.len() == N)
+            .ok_or_else(|| {
+                SudokuError::InvalidLength(format!(
+                    "Row {} of puzzle description grid must have {} cells",
+                    x + 1,
+                    N
+                ))
+            })?;
+
+        for (y, cell) in cells.iter().enumerate() {
+            let coordinate = Coordinate(x as u8, y as u8);
+
+            if let Some(value) = cell.get("value") {
+                let value = value
+                    .as_u64()
+                    .filter(|v| *v >= 1 && *v <= N as u64)
+                    .ok_or_else(|| {
+                        SudokuError::InvalidCellValue(format!(
+                            "Invalid value {} at row {}, col {}",
+                            value,
+                            x + 1,
+                            y + 1
+                        ))
+                    })?;
+                let given = cell.get("given").and_then(Value::as_bool).unwrap_or(false);
+                grid.set_cell_value(coordinate, Some(value as u8 - 1))?;
+                grid.set_cell_editable(coordinate, !given)?;
+            }
+
+            if let Some(region) = cell.get("region") {
+                let (default_region, _, _) = grid.get_cell(coordinate).sub_grid(N as u8);
+                if region.as_u64() != Some(default_region as u64) {
+                    irregular_regions = true;
+                }
+            }
+
+            if has_entries(cell.get("centerPencilMarks"))
+                || has_entries(cell.get("cornerPencilMarks"))
+            {
+                pencil_marks += 1;
+            }
+        }
+    }
+
+    if irregular_regions {
+        unsupported.push(String::from("irregular regions"));
+    }
+    if pencil_marks > 0 {
+        unsupported.push(format!("pencil marks ({} cells)", pencil_marks));
+    }
+    for (key, value) in &description {
+        if SUPPORTED_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        match value {
+            Value::Bool(true) => unsupported.push(key.clone()),
+            Value::Array(items) if !items.is_empty() => {
+                unsupported.push(format!("{} ({})", key, items.len()))
+            }
+            _ => {}
+        }
+    }
+
+    let text = |key: &str| {
+        description
+            .get(key)
+            .and_then(Value::as_str)
+            .map(String::from)
+    };
+
+    Ok(FPuzzle {
+        grid,
+        info: FPuzzleInfo {
+            title: text("title"),
+            author: text("author"),
+            ruleset: text("ruleset"),
+        },
+        unsupported,
+    })
+}
+
+///
+/// Format a grid into an f-puzzles / SudokuPad puzzle description,
+/// LZ-String base64 compressed if `compress`.
+///
+pub fn format_fpuzzles<const N: usize>(
+    grid: &Grid<N>,
+    info: &FPuzzleInfo,
+    compress: bool,
+) -> Result<String, SudokuError> {
+    check_size::<N>()?;
+
+    let rows: Vec<Value> = grid
+        .rows
+        .iter()
+        .map(|row| Value::Array(row.cells.iter().map(format_cell).collect()))
+        .collect();
+
+    let mut description = Map::new();
+    description.insert(String::from("size"), json!(N));
+    description.insert(String::from("grid"), Value::Array(rows));
+    if let Some(title) = &info.title {
+        description.insert(String::from("title"), json!(title));
+    }
+    if let Some(author) = &info.author {
+        description.insert(String::from("author"), json!(author));
+    }
+    if let Some(ruleset) = &info.ruleset {
+        description.insert(String::from("ruleset"), json!(ruleset));
+    }
+
+    let json = Value::Object(description).to_string();
+    if compress {
+        Ok(compress_to_base64(&json))
+    } else {
+        Ok(json)
+    }
+}
+
+fn format_cell(cell: &Cell) -> Value {
+    match cell.value {
+        Some(value) if cell.editable => json!({ "value": value + 1 }),
+        Some(value) => json!({ "value": value + 1, "given": true }),
+        None => json!({}),
+    }
+}
+
+fn has_entries(value: Option<&Value>) -> bool {
+    matches!(value, Some(Value::Array(items)) if !items.is_empty())
+}
+
+/// whether `content` looks like a compressed puzzle description or a url carrying one
+pub(crate) fn is_compressed_description(content: &str) -> bool {
+    let content = content.trim();
+    content.starts_with("N4Ig") || url_payload(content).is_some()
+}
+
+/// raw json of a puzzle description, see `parse_fpuzzles`
+fn decode(content: &str) -> Result<String, SudokuError> {
+    let content = content.trim();
+    if content.starts_with('{') {
+        return Ok(String::from(content));
+    }
+
+    let payload = url_payload(content).unwrap_or(content);
+    let payload = payload
+        .replace("%2B", "+")
+        .replace("%2F", "/")
+        .replace("%3D", "=");
+
+    decompress_from_base64(&payload).ok_or_else(|| {
+        SudokuError::InvalidFormat(String::from("Could not decompress puzzle description"))
+    })
+}
+
+///
+/// Compressed description carried by a puzzle url, either in its `load` query parameter
+/// (`f-puzzles.com/?load=...`) or after a `/fpuzzles` path (`sudokupad.app/fpuzzles...`).
+///
+fn url_payload(url: &str) -> Option<&str> {
+    if url.contains(char::is_whitespace) {
+        return None;
+    }
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let (host, path) = url.split_once('/')?;
+    if !host.contains('.') {
+        return None;
+    }
+
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let load = query
+        .split('&')
+        .find_map(|parameter| parameter.strip_prefix("load="));
+    load.or_else(|| path.strip_prefix("fpuzzles"))
+        .filter(|payload| !payload.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::format_line;
+
+    /// 4x4 description with givens 1 2 in r1 and 4 3 in r4, and a 3 entered at r2c3,
+    /// compressed by the reference lz-string implementation
+    const COMPRESSED: &str = concat!(
+        "N4IgzglgXgpiBcAWANCALhNAbO8QBUIA7ATxFQEMBXNACwHsAnBEAWTlQHNGIATBANoDQANw",
+        "pYquAIxcIImEQRpGkgL7JR4yQgBMs+YvjK1G9cFUBdZMLNmQYibgDMtqzdMfzb85/tbcKCCc",
+        "cgpKKjB2DtrwTvqhRuGWFqpAA===",
+    );
+
+    fn check_example(puzzle: &FPuzzle<4>) {
+        assert_eq!(format_line(&puzzle.grid).unwrap(), "12....3.......43");
+        assert!(!puzzle.grid.get_cell(Coordinate(0, 0)).editable);
+        assert!(puzzle.grid.get_cell(Coordinate(1, 2)).editable);
+        assert_eq!(puzzle.info.title.as_deref(), Some("Tiny"));
+        assert_eq!(puzzle.info.author.as_deref(), Some("Me"));
+        assert!(puzzle.unsupported.is_empty());
+    }
+
+    #[test]
+    fn parses_urls() {
+        let urls = [
+            format!("https://www.f-puzzles.com/?load={}", COMPRESSED),
+            format!("f-puzzles.com/?load={}&solve=1", COMPRESSED),
+            format!("https://sudokupad.app/fpuzzles{}", COMPRESSED),
+            format!(
+                "https://www.f-puzzles.com/?load={}",
+                COMPRESSED.replace('=', "%3D")
+            ),
+            String::from(COMPRESSED),
+        ];
+        for url in &urls {
+            assert!(is_compressed_description(url), "{}", url);
+            check_example(&parse_fpuzzles(url).unwrap());
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = decode(COMPRESSED).unwrap();
+        let puzzle: FPuzzle<4> = parse_fpuzzles(&json).unwrap();
+        check_example(&puzzle);
+
+        for compress in [false, true] {
+            let content = format_fpuzzles(&puzzle.grid, &puzzle.info, compress).unwrap();
+            check_example(&parse_fpuzzles(&content).unwrap());
+        }
+    }
+
+    #[test]
+    fn lists_unsupported_constraints() {
+        let json = r#"{"size":4,"grid":[[{"value":1,"given":true},{},{},{}],
+            [{"centerPencilMarks":[2,3]},{},{},{}],[{},{},{},{}],[{},{},{},{}]],
+            "killercage":[{"cells":["R1C1","R1C2"]},{"cells":["R3C3"]}],"diagonal+":true}"#;
+        let puzzle: FPuzzle<4> = parse_fpuzzles(json).unwrap();
+        assert_eq!(
+            puzzle.unsupported,
+            ["pencil marks (1 cells)", "diagonal+", "killercage (2)"]
+        );
+    }
+
+    #[test]
+    fn rejects_other_sizes() {
+        assert!(matches!(
+            parse_fpuzzles::<9>(COMPRESSED),
+            Err(SudokuError::UnsupportedGridSize(_))
+        ));
+    }
+
+    #[test]
+    fn mentions_are_not_descriptions() {
+        for content in [
+            "fpuzzles_export.txt",
+            "my fpuzzles puzzle",
+            "load=N4Ig",
+            "https://example.com/about",
+        ] {
+            assert!(!is_compressed_description(content), "{}", content);
+        }
+    }
+}
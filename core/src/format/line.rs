@@ -0,0 +1,94 @@
+use super::{check_size, format_row, parse_symbols};
+use crate::error::SudokuError;
+use crate::model::Grid;
+
+///
+/// Parse a grid from the single line format, i.e `N * N` characters read row by row,
+/// where `.` or `0` is a blank cell and any other symbol is a given (non-editable) value.
+///
+/// Values are written `1-9` then `A-P` for grids larger than 9x9.
+///
+pub fn parse_line<const N: usize>(line: &str) -> Result<Grid<N>, SudokuError> {
+    let symbols: Vec<char> = line.trim().chars().collect();
+    parse_symbols(&symbols)
+}
+
+///
+/// Format a grid into the single line format, see `parse_line`.
+/// Blank cells are written as `.`, both given and entered values are written.
+///
+pub fn format_line<const N: usize>(grid: &Grid<N>) -> Result<String, SudokuError> {
+    check_size::<N>()?;
+
+    Ok(grid.rows.iter().map(|row| format_row(row, '.')).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Coordinate;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn round_trip() {
+        let grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        assert_eq!(format_line(&grid).unwrap(), PUZZLE);
+    }
+
+    #[test]
+    fn parses_givens_and_blanks() {
+        let grid: Grid<9> = parse_line(&PUZZLE.replace('.', "0")).unwrap();
+        let given = grid.get_cell(Coordinate(0, 0));
+        assert_eq!(given.value, Some(4));
+        assert!(!given.editable);
+        let blank = grid.get_cell(Coordinate(0, 2));
+        assert_eq!(blank.value, None);
+        assert!(blank.editable);
+    }
+
+    #[test]
+    fn letters_for_large_grids() {
+        let line = format!("G{}", ".".repeat(255));
+        let grid: Grid<16> = parse_line(&line.to_lowercase()).unwrap();
+        assert_eq!(grid.get_cell(Coordinate(0, 0)).value, Some(15));
+        assert_eq!(format_line(&grid).unwrap(), line);
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        match parse_line::<9>(&PUZZLE[1..]) {
+            Err(SudokuError::InvalidLength(message)) => {
+                assert_eq!(message, "Found 80 cells, expected 81 for a 9x9 grid")
+            }
+            _ => panic!("expected an invalid length error"),
+        }
+    }
+
+    #[test]
+    fn rejects_bad_character() {
+        let line = format!("{}x{}", &PUZZLE[..10], &PUZZLE[11..]);
+        match parse_line::<9>(&line) {
+            Err(SudokuError::InvalidCharacter(message)) => assert_eq!(
+                message,
+                "Invalid character 'x' at position 11 (row 2, col 2)"
+            ),
+            _ => panic!("expected an invalid character error"),
+        }
+        // values beyond the grid size are invalid too
+        assert!(matches!(
+            parse_line::<4>("5..............."),
+            Err(SudokuError::InvalidCharacter(_))
+        ));
+    }
+
+    #[test]
+    fn error_lines_of_a_collection() {
+        let error = parse_line::<9>("123").err().unwrap().at_line(3);
+        assert_eq!(
+            error.to_string(),
+            "Line 3: Found 3 cells, expected 81 for a 9x9 grid"
+        );
+    }
+}
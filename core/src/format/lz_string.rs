@@ -0,0 +1,304 @@
+//!
+//! LZ-String compression as used by browser puzzle tools to pack puzzle json into urls,
+//! port of https://github.com/pieroxy/lz-string (`compressToBase64` / `decompressFromBase64`)
+//!
+
+use std::collections::{HashMap, HashSet};
+
+const BASE64_SYMBOLS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
+
+pub fn compress_to_base64(input: &str) -> String {
+    let utf16: Vec<u16> = input.encode_utf16().collect();
+    let mut output = compress(&utf16, 6, |value| BASE64_SYMBOLS[value as usize] as char);
+    match output.len() % 4 {
+        1 => output.push_str("==="),
+        2 => output.push_str("=="),
+        3 => output.push('='),
+        _ => {}
+    }
+    output
+}
+
+/// accepts both the base64 and the uri-component alphabet
+pub fn decompress_from_base64(input: &str) -> Option<String> {
+    let values: Option<Vec<u32>> = input.trim().chars().map(base64_value).collect();
+    let utf16 = decompress(&values?, 32)?;
+    String::from_utf16(&utf16).ok()
+}
+
+fn base64_value(c: char) -> Option<u32> {
+    match c {
+        'A'..='Z' => Some(c as u32 - 'A' as u32),
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 26),
+        '0'..='9' => Some(c as u32 - '0' as u32 + 52),
+        '+' | ' ' => Some(62),
+        '/' | '-' => Some(63),
+        '=' | '$' => Some(64),
+        _ => None,
+    }
+}
+
+struct BitWriter<F: Fn(u32) -> char> {
+    bits_per_char: u32,
+    to_char: F,
+    value: u32,
+    position: u32,
+    output: String,
+}
+
+impl<F: Fn(u32) -> char> BitWriter<F> {
+    fn write_bit(&mut self, bit: u32) {
+        self.value = (self.value << 1) | bit;
+        if self.position == self.bits_per_char - 1 {
+            self.position = 0;
+            self.output.push((self.to_char)(self.value));
+            self.value = 0;
+        } else {
+            self.position += 1;
+        }
+    }
+
+    /// write the lowest `count` bits of `value`, least significant first
+    fn write_bits(&mut self, mut value: u32, count: u32) {
+        for _ in 0..count {
+            self.write_bit(value & 1);
+            value >>= 1;
+        }
+    }
+
+    fn flush(mut self) -> String {
+        loop {
+            self.value <<= 1;
+            if self.position == self.bits_per_char - 1 {
+                self.output.push((self.to_char)(self.value));
+                break;
+            }
+            self.position += 1;
+        }
+        self.output
+    }
+}
+
+struct Compressor<F: Fn(u32) -> char> {
+    dictionary: HashMap<Vec<u16>, u32>,
+    to_create: HashSet<Vec<u16>>,
+    enlarge_in: u32,
+    num_bits: u32,
+    writer: BitWriter<F>,
+}
+
+impl<F: Fn(u32) -> char> Compressor<F> {
+    fn decrement_enlarge_in(&mut self) {
+        self.enlarge_in -= 1;
+        if self.enlarge_in == 0 {
+            self.enlarge_in = 1 << self.num_bits;
+            self.num_bits += 1;
+        }
+    }
+
+    fn emit(&mut self, w: &Vec<u16>) {
+        if self.to_create.remove(w) {
+            if w[0] < 256 {
+                self.writer.write_bits(0, self.num_bits);
+                self.writer.write_bits(w[0] as u32, 8);
+            } else {
+                self.writer.write_bits(1, self.num_bits);
+                self.writer.write_bits(w[0] as u32, 16);
+            }
+            self.decrement_enlarge_in();
+        } else {
+            self.writer.write_bits(self.dictionary[w], self.num_bits);
+        }
+        self.decrement_enlarge_in();
+    }
+}
+
+fn compress<F: Fn(u32) -> char>(input: &[u16], bits_per_char: u32, to_char: F) -> String {
+    let mut compressor = Compressor {
+        dictionary: HashMap::new(),
+        to_create: HashSet::new(),
+        enlarge_in: 2,
+        num_bits: 2,
+        writer: BitWriter {
+            bits_per_char,
+            to_char,
+            value: 0,
+            position: 0,
+            output: String::new(),
+        },
+    };
+    let mut dict_size: u32 = 3;
+    let mut w: Vec<u16> = Vec::new();
+
+    for &c in input {
+        let c = vec![c];
+        if !compressor.dictionary.contains_key(&c) {
+            compressor.dictionary.insert(c.clone(), dict_size);
+            compressor.to_create.insert(c.clone());
+            dict_size += 1;
+        }
+
+        let mut wc = w.clone();
+        wc.extend(&c);
+        if compressor.dictionary.contains_key(&wc) {
+            w = wc;
+        } else {
+            compressor.emit(&w);
+            compressor.dictionary.insert(wc, dict_size);
+            dict_size += 1;
+            w = c;
+        }
+    }
+
+    if !w.is_empty() {
+        compressor.emit(&w);
+    }
+
+    // end of stream
+    compressor.writer.write_bits(2, compressor.num_bits);
+    compressor.writer.flush()
+}
+
+struct BitReader<'a> {
+    input: &'a [u32],
+    reset_value: u32,
+    value: u32,
+    position: u32,
+    index: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn read_bits(&mut self, count: u32) -> u32 {
+        let mut bits = 0;
+        for i in 0..count {
+            let bit = self.value & self.position;
+            self.position >>= 1;
+            if self.position == 0 {
+                self.position = self.reset_value;
+                self.value = self.input.get(self.index).copied().unwrap_or(0);
+                self.index += 1;
+            }
+            if bit > 0 {
+                bits |= 1 << i;
+            }
+        }
+        bits
+    }
+}
+
+fn decompress(input: &[u32], reset_value: u32) -> Option<Vec<u16>> {
+    let mut reader = BitReader {
+        input,
+        reset_value,
+        value: *input.first()?,
+        position: reset_value,
+        index: 1,
+    };
+
+    let mut dictionary: Vec<Vec<u16>> = vec![Vec::new(); 3];
+    let mut enlarge_in: u32 = 4;
+    let mut num_bits: u32 = 3;
+
+    let c = match reader.read_bits(2) {
+        0 => reader.read_bits(8) as u16,
+        1 => reader.read_bits(16) as u16,
+        _ => return Some(Vec::new()),
+    };
+    let mut w = vec![c];
+    dictionary.push(w.clone());
+    let mut result = w.clone();
+
+    loop {
+        if reader.index > input.len() {
+            return None;
+        }
+
+        let mut code = reader.read_bits(num_bits) as usize;
+        match code {
+            0 | 1 => {
+                let bits = if code == 0 { 8 } else { 16 };
+                dictionary.push(vec![reader.read_bits(bits) as u16]);
+                code = dictionary.len() - 1;
+                enlarge_in -= 1;
+            }
+            2 => return Some(result),
+            _ => {}
+        }
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+
+        let entry = if code < dictionary.len() {
+            dictionary[code].clone()
+        } else if code == dictionary.len() {
+            let mut entry = w.clone();
+            entry.push(w[0]);
+            entry
+        } else {
+            return None;
+        };
+
+        result.extend(&entry);
+
+        let mut next = w;
+        next.push(entry[0]);
+        dictionary.push(next);
+        enlarge_in -= 1;
+
+        w = entry;
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_output() {
+        // as produced by the reference javascript implementation
+        assert_eq!(
+            compress_to_base64("Hello, world!"),
+            "BIUwNmD2A0AEDukBOYAmBCIA"
+        );
+        assert_eq!(
+            decompress_from_base64("BIUwNmD2A0AEDukBOYAmBCIA").as_deref(),
+            Some("Hello, world!")
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let inputs = [
+            "",
+            "a",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "{\"size\":9,\"grid\":[[{},{\"value\":5,\"given\":true}]]}",
+            "non ascii: déjà vu, 数独, 🧩",
+        ];
+        for input in inputs {
+            let compressed = compress_to_base64(input);
+            assert_eq!(compressed.len() % 4, 0);
+            assert_eq!(decompress_from_base64(&compressed).as_deref(), Some(input));
+        }
+    }
+
+    #[test]
+    fn uri_alphabet() {
+        assert_eq!(compress_to_base64("sudokuÿ~"), "M4VwJg9g1iD/B+Q=");
+        // `/` is written `-` and padding `$`, a `+` may come back as a space once url decoded
+        for uri in ["M4VwJg9g1iD-B+Q$", "M4VwJg9g1iD-B Q$"] {
+            assert_eq!(decompress_from_base64(uri).as_deref(), Some("sudokuÿ~"));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(decompress_from_base64("not base64!"), None);
+    }
+}
@@ -0,0 +1,104 @@
+mod dimacs;
+mod file_format;
+mod fpuzzles;
+mod line;
+mod lz_string;
+mod sadman;
+mod share;
+mod simple_sudoku;
+
+pub use dimacs::format_dimacs;
+pub use file_format::FileFormat;
+pub use fpuzzles::{format_fpuzzles, parse_fpuzzles, FPuzzle, FPuzzleInfo};
+pub use line::{format_line, parse_line};
+pub use sadman::{format_sdk, format_sdm, parse_sdk, parse_sdm};
+pub use share::{decode_share_code, encode_share_code, is_share_code, SharedGrid};
+pub use simple_sudoku::{format_ss, parse_ss};
+
+use crate::error::SudokuError;
+use crate::model::{CellRow, Coordinate, Grid};
+
+/// symbols used to represent cell values in text formats, index is the cell value
+const VALUE_SYMBOLS: &str = "123456789ABCDEFGHIJKLMNOP";
+
+/// grid sizes that can be represented with `VALUE_SYMBOLS`
+fn check_size<const N: usize>() -> Result<(), SudokuError> {
+    let sub_grid_size = (N as f64).sqrt() as usize;
+    if N == 0 || sub_grid_size * sub_grid_size != N || N > VALUE_SYMBOLS.len() {
+        return Err(SudokuError::UnsupportedGridSize(format!(
+            "Grid size {} is not supported. Size must be a perfect square in range [1, {}]",
+            N,
+            VALUE_SYMBOLS.len()
+        )));
+    }
+
+    Ok(())
+}
+
+fn value_to_char(value: u8) -> char {
+    VALUE_SYMBOLS.as_bytes()[value as usize] as char
+}
+
+fn char_to_value<const N: usize>(c: char) -> Option<u8> {
+    let upper = c.to_ascii_uppercase();
+    VALUE_SYMBOLS[..N].find(upper).map(|i| i as u8)
+}
+
+fn is_blank_char(c: char) -> bool {
+    c == '.' || c == '0'
+}
+
+///
+/// Build a grid from `N * N` cell symbols read row by row,
+/// blank symbols are skipped and any other symbol becomes a given (non-editable) value
+///
+fn parse_symbols<const N: usize>(symbols: &[char]) -> Result<Grid<N>, SudokuError> {
+    check_size::<N>()?;
+
+    if symbols.len() != N * N {
+        return Err(SudokuError::InvalidLength(format!(
+            "Found {} cells, expected {} for a {}x{} grid",
+            symbols.len(),
+            N * N,
+            N,
+            N
+        )));
+    }
+
+    let mut grid: Grid<N> = Grid::new();
+    for (i, c) in symbols.iter().enumerate() {
+        if is_blank_char(*c) {
+            continue;
+        }
+
+        let coordinate = Coordinate((i / N) as u8, (i % N) as u8);
+        match char_to_value::<N>(*c) {
+            Some(value) => {
+                grid.set_cell_value(coordinate, Some(value))?;
+                grid.set_cell_editable(coordinate, false)?;
+            }
+            None => {
+                return Err(SudokuError::InvalidCharacter(format!(
+                    "Invalid character '{}' at position {} (row {}, col {})",
+                    c,
+                    i + 1,
+                    i / N + 1,
+                    i % N + 1
+                )));
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
+/// write the cells of `row` as symbols, blank cells as `blank`
+fn format_row<const N: usize>(row: &CellRow<N>, blank: char) -> String {
+    row.cells
+        .iter()
+        .map(|cell| match cell.value {
+            Some(value) => value_to_char(value),
+            None => blank,
+        })
+        .collect()
+}
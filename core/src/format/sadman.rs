@@ -0,0 +1,129 @@
+use super::{check_size, format_row, parse_symbols};
+use crate::error::SudokuError;
+use crate::model::Grid;
+
+///
+/// Parse a grid from the SadMan Sudoku (`.sdk`) format, i.e one row of cell symbols per line.
+///
+/// Metadata lines starting with `#` are ignored. For files with sections,
+/// only the `[Puzzle]` section is read.
+///
+pub fn parse_sdk<const N: usize>(content: &str) -> Result<Grid<N>, SudokuError> {
+    let has_sections = content.lines().any(|line| line.trim() == "[Puzzle]");
+    let mut in_puzzle = !has_sections;
+
+    let mut symbols: Vec<char> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_puzzle = line == "[Puzzle]";
+            continue;
+        }
+        if !in_puzzle || line.starts_with('#') {
+            continue;
+        }
+        symbols.extend(line.chars().filter(|c| !c.is_whitespace()));
+    }
+
+    parse_symbols(&symbols)
+}
+
+/// Format a grid into the SadMan Sudoku (`.sdk`) format, see `parse_sdk`
+pub fn format_sdk<const N: usize>(grid: &Grid<N>) -> Result<String, SudokuError> {
+    check_size::<N>()?;
+
+    let mut content = String::new();
+    for row in &grid.rows {
+        content.push_str(&format_row(row, '.'));
+        content.push('\n');
+    }
+
+    Ok(content)
+}
+
+///
+/// Parse grids from the SadMan Sudoku multi-puzzle (`.sdm`) format,
+/// i.e one puzzle per line in the single line format (see `parse_line`).
+///
+/// Blank lines and lines starting with `#` are ignored.
+///
+pub fn parse_sdm<const N: usize>(content: &str) -> Result<Vec<Grid<N>>, SudokuError> {
+    let mut grids: Vec<Grid<N>> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let symbols: Vec<char> = line.chars().collect();
+        let grid = parse_symbols(&symbols).map_err(|error| error.at_line(i + 1))?;
+        grids.push(grid);
+    }
+
+    Ok(grids)
+}
+
+/// Format grids into the SadMan Sudoku multi-puzzle (`.sdm`) format, see `parse_sdm`
+pub fn format_sdm<const N: usize>(grids: &[Grid<N>]) -> Result<String, SudokuError> {
+    check_size::<N>()?;
+
+    let mut content = String::new();
+    for grid in grids {
+        for row in &grid.rows {
+            content.push_str(&format_row(row, '0'));
+        }
+        content.push('\n');
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn sdk_round_trip() {
+        let grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        let content = format_sdk(&grid).unwrap();
+        assert_eq!(content.lines().next(), Some("53..7...."));
+        let parsed: Grid<9> = parse_sdk(&content).unwrap();
+        assert_eq!(format_line(&parsed).unwrap(), PUZZLE);
+    }
+
+    #[test]
+    fn sdk_reads_puzzle_section_only() {
+        let content = "[Properties]\n#A author\n[Puzzle]\n12..\n....\n....\n..43\n[State]\n1234\n";
+        let grid: Grid<4> = parse_sdk(content).unwrap();
+        assert_eq!(format_line(&grid).unwrap(), "12............43");
+    }
+
+    #[test]
+    fn sdm_round_trip() {
+        let grids: Vec<Grid<4>> = vec![
+            parse_line("12............43").unwrap(),
+            parse_line("...4............").unwrap(),
+        ];
+        let content = format_sdm(&grids).unwrap();
+        assert_eq!(content, "1200000000000043\n0004000000000000\n");
+        let parsed: Vec<Grid<4>> = parse_sdm(&format!("# two puzzles\n\n{}", content)).unwrap();
+        let lines: Vec<String> = parsed
+            .iter()
+            .map(|grid| format_line(grid).unwrap())
+            .collect();
+        assert_eq!(lines, ["12............43", "...4............"]);
+    }
+
+    #[test]
+    fn sdm_error_names_the_line() {
+        let error = parse_sdm::<4>("1200000000000043\n12\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Line 2: Found 2 cells, expected 16 for a 4x4 grid"
+        );
+    }
+}
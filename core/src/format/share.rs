@@ -0,0 +1,360 @@
+//!
+//! Compact url-safe share codes: `sk-` followed by base64url (no padding) of
+//!
+//! ```text
+//! [version][size][flags] [bit stream] [crc16 hi][crc16 lo]
+//! ```
+//!
+//! where the bit stream holds one presence bit per cell, then for each filled cell
+//! its value and a given bit, then (if flagged) notes of blank cells.
+//!
+
+use super::check_size;
+use crate::error::SudokuError;
+use crate::model::{Coordinate, Grid, Notes};
+
+const PREFIX: &str = "sk-";
+const VERSION: u8 = 1;
+const FLAG_NOTES: u8 = 0b0000_0001;
+const BASE64_URL_SYMBOLS: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// grid and notes decoded from a share code
+pub struct SharedGrid<const N: usize> {
+    pub grid: Grid<N>,
+    pub notes: Option<Notes<N>>,
+}
+
+pub fn is_share_code(content: &str) -> bool {
+    content.trim().starts_with(PREFIX)
+}
+
+/// Encode givens, player entries and (optionally) notes of a grid into a share code
+pub fn encode_share_code<const N: usize>(
+    grid: &Grid<N>,
+    notes: Option<&Notes<N>>,
+) -> Result<String, SudokuError> {
+    check_size::<N>()?;
+
+    let notes = notes.filter(|notes| !notes.is_empty());
+    let value_bits = value_bits::<N>();
+
+    let mut writer = BitWriter::new();
+    for row in &grid.rows {
+        for cell in &row.cells {
+            writer.write(cell.value.is_some() as u32, 1);
+        }
+    }
+    for row in &grid.rows {
+        for cell in &row.cells {
+            if let Some(value) = cell.value {
+                writer.write(value as u32, value_bits);
+                writer.write(!cell.editable as u32, 1);
+            }
+        }
+    }
+    if let Some(notes) = notes {
+        for row in &grid.rows {
+            for cell in row.cells.iter().filter(|cell| cell.value.is_none()) {
+                let mask = notes.mask(cell.coordinate);
+                writer.write((mask != 0) as u32, 1);
+                if mask != 0 {
+                    writer.write(mask, N as u32);
+                }
+            }
+        }
+    }
+
+    let flags = if notes.is_some() { FLAG_NOTES } else { 0 };
+    let mut bytes = vec![VERSION, N as u8, flags];
+    bytes.extend(writer.finish());
+    let checksum = crc16(&bytes);
+    bytes.extend(checksum.to_be_bytes());
+
+    Ok(format!("{}{}", PREFIX, encode_base64_url(&bytes)))
+}
+
+/// Decode a share code, see `encode_share_code`
+pub fn decode_share_code<const N: usize>(code: &str) -> Result<SharedGrid<N>, SudokuError> {
+    check_size::<N>()?;
+
+    let body = code
+        .trim()
+        .strip_prefix(PREFIX)
+        .ok_or_else(|| invalid(&format!("must start with '{}'", PREFIX)))?;
+    let bytes = decode_base64_url(body).ok_or_else(|| invalid("contains invalid characters"))?;
+    if bytes.len() < 5 {
+        return Err(invalid("is too short"));
+    }
+
+    let (payload, checksum) = bytes.split_at(bytes.len() - 2);
+    if crc16(payload).to_be_bytes() != checksum {
+        return Err(invalid("checksum does not match, code may be incomplete"));
+    }
+
+    let (version, size, flags) = (payload[0], payload[1], payload[2]);
+    if version != VERSION {
+        return Err(invalid(&format!("version {} is not supported", version)));
+    }
+    if size as usize != N {
+        return Err(SudokuError::UnsupportedGridSize(format!(
+            "Share code is for a {}x{} grid, expected {}x{}",
+            size, size, N, N
+        )));
+    }
+
+    let value_bits = value_bits::<N>();
+    let mut reader = BitReader::new(&payload[3..]);
+    let truncated = || invalid("is truncated");
+
+    let mut filled: Vec<Coordinate> = Vec::new();
+    for x in 0..N {
+        for y in 0..N {
+            if reader.read(1).ok_or_else(truncated)? == 1 {
+                filled.push(Coordinate(x as u8, y as u8));
+            }
+        }
+    }
+
+    let mut grid: Grid<N> = Grid::new();
+    for coordinate in &filled {
+        let value = reader.read(value_bits).ok_or_else(truncated)?;
+        let given = reader.read(1).ok_or_else(truncated)? == 1;
+        if value as usize >= N {
+            return Err(invalid("contains an invalid cell value"));
+        }
+        grid.set_cell_value(*coordinate, Some(value as u8))?;
+        grid.set_cell_editable(*coordinate, !given)?;
+    }
+
+    let mut notes: Option<Notes<N>> = None;
+    if flags & FLAG_NOTES != 0 {
+        let mut cell_notes: Notes<N> = Notes::new();
+        for x in 0..N {
+            for y in 0..N {
+                let coordinate = Coordinate(x as u8, y as u8);
+                if filled.contains(&coordinate) {
+                    continue;
+                }
+                if reader.read(1).ok_or_else(truncated)? == 1 {
+                    cell_notes.set_mask(coordinate, reader.read(N as u32).ok_or_else(truncated)?);
+                }
+            }
+        }
+        notes = Some(cell_notes);
+    }
+
+    Ok(SharedGrid { grid, notes })
+}
+
+fn invalid(reason: &str) -> SudokuError {
+    SudokuError::InvalidFormat(format!("Share code {}", reason))
+}
+
+/// bits needed to write a value in range [0, N)
+fn value_bits<const N: usize>() -> u32 {
+    usize::BITS - (N.max(2) - 1).leading_zeros()
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_count: usize,
+}
+
+impl BitWriter {
+    fn new() -> BitWriter {
+        BitWriter {
+            bytes: Vec::new(),
+            bit_count: 0,
+        }
+    }
+
+    /// write the lowest `count` bits of `value`, most significant first
+    fn write(&mut self, value: u32, count: u32) {
+        for i in (0..count).rev() {
+            if self.bit_count / 8 == self.bytes.len() {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0b1000_0000 >> (self.bit_count % 8);
+            }
+            self.bit_count += 1;
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_count: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> BitReader<'a> {
+        BitReader {
+            bytes,
+            bit_count: 0,
+        }
+    }
+
+    fn read(&mut self, count: u32) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..count {
+            let byte = self.bytes.get(self.bit_count / 8)?;
+            let bit = (byte >> (7 - self.bit_count % 8)) & 1;
+            value = (value << 1) | bit as u32;
+            self.bit_count += 1;
+        }
+        Some(value)
+    }
+}
+
+/// CRC-16/CCITT-FALSE
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn encode_base64_url(bytes: &[u8]) -> String {
+    let mut output = String::new();
+    for chunk in bytes.chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let group = (buffer[0] as u32) << 16 | (buffer[1] as u32) << 8 | buffer[2] as u32;
+        for i in 0..=chunk.len() {
+            let index = (group >> (18 - 6 * i)) & 0b11_1111;
+            output.push(BASE64_URL_SYMBOLS[index as usize] as char);
+        }
+    }
+    output
+}
+
+fn decode_base64_url(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in input.bytes() {
+        let value = BASE64_URL_SYMBOLS.iter().position(|s| *s == c)? as u32;
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    /// puzzle with an entry at r1c3 and notes at r1c4
+    fn example() -> (Grid<9>, Notes<9>) {
+        let mut grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        grid.set_cell_value(Coordinate(0, 2), Some(3)).unwrap();
+        let mut notes: Notes<9> = Notes::new();
+        notes.set(Coordinate(0, 3), &[1, 5, 8]);
+        (grid, notes)
+    }
+
+    fn assert_same(decoded: &Grid<9>, grid: &Grid<9>) {
+        assert_eq!(format_line(decoded).unwrap(), format_line(grid).unwrap());
+        for row in &grid.rows {
+            for cell in &row.cells {
+                assert_eq!(decoded.get_cell(cell.coordinate).editable, cell.editable);
+            }
+        }
+    }
+
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn round_trip() {
+        let (grid, _) = example();
+        let code = encode_share_code(&grid, None).unwrap();
+        assert!(is_share_code(&code));
+        assert!(code[PREFIX.len()..]
+            .bytes()
+            .all(|c| BASE64_URL_SYMBOLS.contains(&c)));
+        let shared: SharedGrid<9> = decode_share_code(&code).unwrap();
+        assert_same(&shared.grid, &grid);
+        assert_eq!(shared.notes, None);
+    }
+
+    #[test]
+    fn round_trip_with_notes() {
+        let (grid, notes) = example();
+        let code = encode_share_code(&grid, Some(&notes)).unwrap();
+        let shared: SharedGrid<9> = decode_share_code(&code).unwrap();
+        assert_same(&shared.grid, &grid);
+        assert_eq!(shared.notes, Some(notes));
+
+        // empty notes are not encoded
+        let without = encode_share_code(&grid, Some(&Notes::new())).unwrap();
+        assert_eq!(without, encode_share_code(&grid, None).unwrap());
+    }
+
+    #[test]
+    fn round_trip_large_grid() {
+        let mut grid: Grid<16> = Grid::new();
+        grid.set_cell_value(Coordinate(15, 15), Some(15)).unwrap();
+        grid.set_cell_editable(Coordinate(15, 15), false).unwrap();
+        let code = encode_share_code(&grid, None).unwrap();
+        let shared: SharedGrid<16> = decode_share_code(&code).unwrap();
+        assert_eq!(
+            format_line(&shared.grid).unwrap(),
+            format_line(&grid).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_corrupted_codes() {
+        let (grid, notes) = example();
+        let code = encode_share_code(&grid, Some(&notes)).unwrap();
+        for i in PREFIX.len()..code.len() {
+            let mut corrupted = code.clone().into_bytes();
+            corrupted[i] = if corrupted[i] == b'A' { b'B' } else { b'A' };
+            let corrupted = String::from_utf8(corrupted).unwrap();
+            assert!(
+                decode_share_code::<9>(&corrupted).is_err(),
+                "{} accepted",
+                corrupted
+            );
+        }
+        assert!(decode_share_code::<9>(&code[..code.len() - 3]).is_err());
+        assert!(decode_share_code::<9>(&code.replace("sk-", "sc-")).is_err());
+    }
+
+    #[test]
+    fn rejects_other_sizes() {
+        let (grid, _) = example();
+        let code = encode_share_code(&grid, None).unwrap();
+        assert!(matches!(
+            decode_share_code::<4>(&code),
+            Err(SudokuError::UnsupportedGridSize(_))
+        ));
+    }
+}
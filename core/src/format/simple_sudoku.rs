@@ -0,0 +1,77 @@
+use super::{check_size, format_row, parse_symbols};
+use crate::error::SudokuError;
+use crate::model::Grid;
+
+///
+/// Parse a grid from the Simple Sudoku (`.ss`) format, i.e rows of cell symbols where
+/// sub grids are separated by `|` columns and `-` lines:
+///
+/// ```text
+/// 53.|.7.|...
+/// 6..|195|...
+/// .98|...|.6.
+/// -----------
+/// ...
+/// ```
+///
+pub fn parse_ss<const N: usize>(content: &str) -> Result<Grid<N>, SudokuError> {
+    let symbols: Vec<char> = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(|line| line.chars())
+        .filter(|c| !c.is_whitespace() && !matches!(c, '|' | '-' | '+'))
+        .collect();
+
+    parse_symbols(&symbols)
+}
+
+/// Format a grid into the Simple Sudoku (`.ss`) format, see `parse_ss`
+pub fn format_ss<const N: usize>(grid: &Grid<N>) -> Result<String, SudokuError> {
+    check_size::<N>()?;
+
+    let sub_grid_size = (N as f64).sqrt() as usize;
+    let separator = "-".repeat(N + sub_grid_size - 1);
+
+    let mut content = String::new();
+    for (i, row) in grid.rows.iter().enumerate() {
+        if i > 0 && i % sub_grid_size == 0 {
+            content.push_str(&separator);
+            content.push('\n');
+        }
+
+        let symbols: Vec<char> = format_row(row, '.').chars().collect();
+        let groups: Vec<String> = symbols
+            .chunks(sub_grid_size)
+            .map(|chunk| chunk.iter().collect())
+            .collect();
+        content.push_str(&groups.join("|"));
+        content.push('\n');
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn round_trip() {
+        let grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        let content = format_ss(&grid).unwrap();
+        assert!(content.starts_with("53.|.7.|...\n6..|195|...\n.98|...|.6.\n-----------\n"));
+        let parsed: Grid<9> = parse_ss(&content).unwrap();
+        assert_eq!(format_line(&parsed).unwrap(), PUZZLE);
+    }
+
+    #[test]
+    fn ignores_comments_and_separators() {
+        let content = "# from a book\n12|..\n..|..\n--+--\n..|..\n..|43\n";
+        let grid: Grid<4> = parse_ss(content).unwrap();
+        assert_eq!(format_line(&grid).unwrap(), "12............43");
+    }
+}
@@ -0,0 +1,270 @@
+use crate::analysis::grade;
+use crate::error::SudokuError;
+use crate::model::{Coordinate, Difficulty, Grid, Symmetry};
+use crate::solver::{Backtracking, Solver};
+use crate::transform::Transform;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone)]
+pub struct GeneratorOptions {
+    pub difficulty: RangeInclusive<Difficulty>,
+    pub symmetry: Symmetry,
+    pub clues: RangeInclusive<usize>,
+    pub attempts: usize, // puzzles tried per `generate` before giving up
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::Easy..=Difficulty::Expert,
+            symmetry: Symmetry::None,
+            clues: 0..=usize::MAX,
+            attempts: 1000,
+        }
+    }
+}
+
+/// Puzzle with a unique solution, givens are not editable
+pub struct Generated<const N: usize> {
+    pub puzzle: Grid<N>,
+    pub solution: Grid<N>,
+    pub difficulty: Difficulty,
+}
+
+///
+/// Generates puzzles by removing clues from a random solution (in random order,
+/// together with their symmetry orbit) while the solution stays unique,
+/// the difficulty stays within range and the clue count stays above the minimum.
+///
+pub struct Generator<R: Rng> {
+    rng: R,
+    options: GeneratorOptions,
+}
+
+impl Generator<StdRng> {
+    /// same seed and options produce the same puzzles
+    pub fn from_seed(seed: u64, options: GeneratorOptions) -> Generator<StdRng> {
+        Generator::new(StdRng::seed_from_u64(seed), options)
+    }
+}
+
+impl<R: Rng> Generator<R> {
+    pub fn new(rng: R, options: GeneratorOptions) -> Generator<R> {
+        Generator { rng, options }
+    }
+
+    pub fn generate<const N: usize>(&mut self) -> Result<Generated<N>, SudokuError> {
+        for _ in 0..self.options.attempts {
+            if let Some(generated) = self.attempt() {
+                return Ok(generated);
+            }
+        }
+
+        Err(SudokuError::GenerationFailed(format!(
+            "No {}x{} puzzle with difficulty {}..{} and {}..{} clues found in {} attempts",
+            N,
+            N,
+            self.options.difficulty.start(),
+            self.options.difficulty.end(),
+            self.options.clues.start(),
+            self.options.clues.end().min(&(N * N)),
+            self.options.attempts
+        )))
+    }
+
+    fn attempt<const N: usize>(&mut self) -> Option<Generated<N>> {
+        let mut solution: Grid<N> = self.solution();
+        let mut puzzle = solution.clone();
+        let mut coordinates: Vec<Coordinate> = Vec::new();
+        for row in 0..N as u8 {
+            for col in 0..N as u8 {
+                coordinates.push(Coordinate(row, col));
+                puzzle
+                    .set_cell_editable(Coordinate(row, col), false)
+                    .unwrap();
+            }
+        }
+        coordinates.shuffle(&mut self.rng);
+
+        let min_clues = *self.options.clues.start();
+        let max_difficulty = *self.options.difficulty.end();
+        let mut clues = N * N;
+        for coordinate in coordinates {
+            if clues <= min_clues {
+                break;
+            }
+            if puzzle.get_cell(coordinate).value.is_none() {
+                // already removed with its orbit
+                continue;
+            }
+
+            let orbit = self.options.symmetry.orbit(coordinate, N as u8);
+            if clues < min_clues + orbit.len() {
+                continue;
+            }
+            let mut reduced = puzzle.clone();
+            for coordinate in &orbit {
+                reduced.set_cell_value(*coordinate, None).unwrap();
+                reduced.set_cell_editable(*coordinate, true).unwrap();
+            }
+            if Backtracking.count_solutions(&reduced, 2) != 1 {
+                continue;
+            }
+            if max_difficulty < Difficulty::Expert && grade(&reduced) > max_difficulty {
+                continue;
+            }
+
+            puzzle = reduced;
+            clues -= orbit.len();
+        }
+
+        let difficulty = grade(&puzzle);
+        if clues > *self.options.clues.end() || !self.options.difficulty.contains(&difficulty) {
+            return None;
+        }
+
+        for row in puzzle.rows.iter() {
+            for cell in row.cells.iter() {
+                solution
+                    .set_cell_editable(cell.coordinate, cell.editable)
+                    .unwrap();
+            }
+        }
+
+        Some(Generated {
+            puzzle,
+            solution,
+            difficulty,
+        })
+    }
+
+    /// random solved grid
+    fn solution<const N: usize>(&mut self) -> Grid<N> {
+        // sub grids on the diagonal never share a row or column, so any values fit,
+        // though small grids may be left without a solution
+        let size = (N as f64).sqrt() as usize;
+        let solution = loop {
+            let mut grid: Grid<N> = Grid::new();
+            for b in 0..size {
+                let mut values: Vec<u8> = (0..N as u8).collect();
+                values.shuffle(&mut self.rng);
+                for (i, value) in values.into_iter().enumerate() {
+                    let coordinate =
+                        Coordinate((b * size + i / size) as u8, (b * size + i % size) as u8);
+                    grid.set_cell_value(coordinate, Some(value)).unwrap();
+                }
+            }
+
+            if let Some(solution) = Backtracking.solve(&grid) {
+                break solution;
+            }
+        };
+
+        match Transform::shuffle::<N, R>(&mut self.rng).apply(&solution) {
+            Ok(transformed) => transformed.grid,
+            Err(_) => solution,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::format_line;
+
+    /// check the puzzle against its solution and the options it was generated with
+    fn check_generated<const N: usize>(generated: &Generated<N>, options: &GeneratorOptions) {
+        let Generated {
+            puzzle,
+            solution,
+            difficulty,
+        } = generated;
+        assert_eq!(Backtracking.count_solutions(puzzle, 2), 1);
+        assert_eq!(
+            format_line(&Backtracking.solve(puzzle).unwrap()).unwrap(),
+            format_line(solution).unwrap()
+        );
+        assert_eq!(grade(puzzle), *difficulty);
+        assert!(options.difficulty.contains(difficulty));
+
+        let mut clues = 0;
+        for row in &puzzle.rows {
+            for cell in &row.cells {
+                let solved = solution.get_cell(cell.coordinate);
+                assert_eq!(cell.editable, cell.value.is_none());
+                assert_eq!(solved.editable, cell.editable);
+                if cell.value.is_some() {
+                    assert_eq!(cell.value, solved.value);
+                    clues += 1;
+                }
+                // the whole orbit of a cell is either given or blank
+                for other in options.symmetry.orbit(cell.coordinate, N as u8) {
+                    assert_eq!(puzzle.get_cell(other).value.is_some(), cell.value.is_some());
+                }
+            }
+        }
+        assert!(options.clues.contains(&clues), "{} clues", clues);
+    }
+
+    #[test]
+    fn puzzles_keep_symmetry_and_uniqueness() {
+        for (seed, symmetry) in Symmetry::ALL.into_iter().enumerate() {
+            let options = GeneratorOptions {
+                symmetry,
+                ..GeneratorOptions::default()
+            };
+            let mut generator = Generator::from_seed(seed as u64, options.clone());
+            for _ in 0..3 {
+                check_generated(&generator.generate::<4>().unwrap(), &options);
+            }
+            check_generated(&generator.generate::<9>().unwrap(), &options);
+        }
+    }
+
+    #[test]
+    fn puzzles_match_difficulty_and_clues() {
+        let cases = [
+            (Difficulty::Easy..=Difficulty::Easy, 30..=36),
+            (Difficulty::Medium..=Difficulty::Hard, 0..=usize::MAX),
+        ];
+        for (seed, (difficulty, clues)) in cases.into_iter().enumerate() {
+            let options = GeneratorOptions {
+                difficulty,
+                symmetry: Symmetry::Rotational,
+                clues,
+                ..GeneratorOptions::default()
+            };
+            let generated = Generator::from_seed(seed as u64, options.clone())
+                .generate::<9>()
+                .unwrap();
+            check_generated(&generated, &options);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_puzzles() {
+        let puzzles = |seed| {
+            let mut generator = Generator::from_seed(seed, GeneratorOptions::default());
+            (0..3)
+                .map(|_| format_line(&generator.generate::<9>().unwrap().puzzle).unwrap())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(puzzles(38), puzzles(38));
+        assert_ne!(puzzles(38), puzzles(39));
+    }
+
+    #[test]
+    fn gives_up_on_impossible_options() {
+        // a 4x4 puzzle needs at least 4 clues
+        let options = GeneratorOptions {
+            clues: 0..=3,
+            attempts: 5,
+            ..GeneratorOptions::default()
+        };
+        let result = Generator::from_seed(0, options).generate::<4>();
+        assert!(matches!(result, Err(SudokuError::GenerationFailed(_))));
+    }
+}
@@ -1,5 +1,12 @@
+pub mod analysis;
+pub mod batch;
+pub mod collection;
 pub mod error;
+pub mod format;
+pub mod generator;
 pub mod model;
+pub mod solver;
+pub mod transform;
 
 #[cfg(test)]
 mod tests {
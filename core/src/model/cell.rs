@@ -24,7 +24,8 @@ impl Cell {
         let sub_grid_y = y / sub_grid_size;
 
         let sub_grid_index = (sub_grid_x * sub_grid_size + sub_grid_y) as usize;
-        let cell_index_in_sub_grid = ((x % sub_grid_size * 3) + (y % sub_grid_size)) as usize;
+        let cell_index_in_sub_grid =
+            ((x % sub_grid_size * sub_grid_size) + (y % sub_grid_size)) as usize;
 
         (
             sub_grid_index,
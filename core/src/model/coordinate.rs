@@ -29,3 +29,10 @@ impl std::ops::Add for Coordinate {
         Self(row1 + row2, col1 + col2)
     }
 }
+
+/// 1-based, e.g. `r1c9` for the top right cell of a 9x9 grid
+impl std::fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "r{}c{}", self.row() + 1, self.col() + 1)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::SudokuError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// difficulty of a puzzle by the hardest technique needed to solve it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Difficulty {
+    Easy,   // singles only
+    Medium, // locked candidates, pairs
+    Hard,   // triples, wings, fish
+    Expert, // not solvable with known techniques, requires guessing
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = SudokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Difficulty::ALL
+            .iter()
+            .find(|difficulty| difficulty.to_string() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| {
+                SudokuError::InvalidFormat(format!(
+                    "Unknown difficulty {}. Expected one of easy, medium, hard, expert",
+                    s
+                ))
+            })
+    }
+}
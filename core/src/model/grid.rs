@@ -32,6 +32,18 @@ pub struct Grid<const N: usize> {
     event_emitter: EventEmitter,
 }
 
+/// cloned grid starts without event listeners
+impl<const N: usize> Clone for Grid<N> {
+    fn clone(&self) -> Self {
+        Grid {
+            rows: self.rows,
+            sub_grids: self.sub_grids,
+            value_map: self.value_map.clone(),
+            event_emitter: EventEmitter::new(),
+        }
+    }
+}
+
 impl<const N: usize> Grid<N> {
     pub fn new() -> Grid<N> {
         let mut grid = Grid {
@@ -201,6 +213,24 @@ impl<const N: usize> Grid<N> {
         Ok(())
     }
 
+    pub fn set_cell_editable(
+        &mut self,
+        coordinate: Coordinate,
+        editable: bool,
+    ) -> Result<(), SudokuError> {
+        let Coordinate(x, y) = coordinate;
+        if usize::from(x) >= N || usize::from(y) >= N {
+            return Err(SudokuError::CellCoordinateOutOfBound(format!(
+                "Coordinate (x: {}, y: {}) is invalid. x,y must be in range [0, {})",
+                x, y, N
+            )));
+        }
+
+        self.get_cell_mut(coordinate).editable = editable;
+
+        Ok(())
+    }
+
     pub fn on<F, T>(&mut self, event: SudokuEventType, callback: F) -> String
     where
         for<'de> T: Deserialize<'de>,
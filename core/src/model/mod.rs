@@ -1,11 +1,17 @@
 mod cell;
 mod coordinate;
+mod difficulty;
 mod enums;
 mod grid;
+mod notes;
 mod sub_grid;
+mod symmetry;
 
 pub use cell::Cell;
 pub use coordinate::Coordinate;
+pub use difficulty::Difficulty;
 pub use enums::{CellRelation, SudokuEventType};
-pub use grid::Grid;
+pub use grid::{CellRow, Grid};
+pub use notes::Notes;
 pub use sub_grid::SubGrid;
+pub use symmetry::Symmetry;
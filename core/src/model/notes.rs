@@ -0,0 +1,61 @@
+use super::Coordinate;
+use serde::{Deserialize, Serialize};
+
+/// pencil marks of a grid, noted values of each cell are kept as a bit mask
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Notes<const N: usize> {
+    marks: Vec<u32>, // row by row, bit `i` set if value `i` is noted
+}
+
+impl<const N: usize> Notes<N> {
+    pub fn new() -> Notes<N> {
+        Notes {
+            marks: vec![0; N * N],
+        }
+    }
+
+    pub fn get(&self, coordinate: Coordinate) -> Vec<u8> {
+        let mask = self.mask(coordinate);
+        (0..N as u8).filter(|v| mask & (1 << v) != 0).collect()
+    }
+
+    pub fn has(&self, coordinate: Coordinate, value: u8) -> bool {
+        self.mask(coordinate) & (1 << value) != 0
+    }
+
+    pub fn toggle(&mut self, coordinate: Coordinate, value: u8) {
+        self.marks[Self::index(coordinate)] ^= 1 << value;
+    }
+
+    pub fn set(&mut self, coordinate: Coordinate, values: &[u8]) {
+        self.marks[Self::index(coordinate)] = values.iter().fold(0, |mask, v| mask | (1 << v));
+    }
+
+    pub fn clear(&mut self, coordinate: Coordinate) {
+        self.marks[Self::index(coordinate)] = 0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.marks.iter().all(|mask| *mask == 0)
+    }
+
+    /// bit mask of noted values, bit `i` set if value `i` is noted
+    pub fn mask(&self, coordinate: Coordinate) -> u32 {
+        self.marks[Self::index(coordinate)]
+    }
+
+    pub fn set_mask(&mut self, coordinate: Coordinate, mask: u32) {
+        self.marks[Self::index(coordinate)] = mask;
+    }
+
+    fn index(coordinate: Coordinate) -> usize {
+        let Coordinate(x, y) = coordinate;
+        x as usize * N + y as usize
+    }
+}
+
+impl<const N: usize> Default for Notes<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
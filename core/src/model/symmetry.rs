@@ -0,0 +1,135 @@
+use super::Coordinate;
+use crate::error::SudokuError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// symmetry of the clue layout of a puzzle
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Symmetry {
+    None,
+    Rotational,        // 180° rotation
+    QuarterRotational, // 90° rotation
+    LeftRight,         // mirror columns
+    TopBottom,         // mirror rows
+    Diagonal,          // mirror along the top-left to bottom-right diagonal
+    AntiDiagonal,      // mirror along the top-right to bottom-left diagonal
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 7] = [
+        Symmetry::None,
+        Symmetry::Rotational,
+        Symmetry::QuarterRotational,
+        Symmetry::LeftRight,
+        Symmetry::TopBottom,
+        Symmetry::Diagonal,
+        Symmetry::AntiDiagonal,
+    ];
+
+    /// cells mapped onto each other by the symmetry, starting with `coordinate`
+    pub fn orbit(&self, coordinate: Coordinate, size: u8) -> Vec<Coordinate> {
+        let n = size - 1;
+        let mut orbit = vec![coordinate];
+        let mut current = coordinate;
+        loop {
+            let Coordinate(r, c) = current;
+            current = match self {
+                Symmetry::None => return orbit,
+                Symmetry::Rotational => Coordinate(n - r, n - c),
+                Symmetry::QuarterRotational => Coordinate(c, n - r),
+                Symmetry::LeftRight => Coordinate(r, n - c),
+                Symmetry::TopBottom => Coordinate(n - r, c),
+                Symmetry::Diagonal => Coordinate(c, r),
+                Symmetry::AntiDiagonal => Coordinate(n - c, n - r),
+            };
+            if orbit.contains(&current) {
+                return orbit;
+            }
+            orbit.push(current);
+        }
+    }
+}
+
+impl std::fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Symmetry::None => "none",
+            Symmetry::Rotational => "rotational",
+            Symmetry::QuarterRotational => "quarter-rotational",
+            Symmetry::LeftRight => "left-right",
+            Symmetry::TopBottom => "top-bottom",
+            Symmetry::Diagonal => "diagonal",
+            Symmetry::AntiDiagonal => "anti-diagonal",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = SudokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Symmetry::ALL
+            .iter()
+            .find(|symmetry| symmetry.to_string() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| SudokuError::InvalidFormat(format!("Unknown symmetry {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orbits() {
+        let orbit = |symmetry: Symmetry, r: u8, c: u8| symmetry.orbit(Coordinate(r, c), 9);
+        assert_eq!(orbit(Symmetry::None, 0, 1), [Coordinate(0, 1)]);
+        assert_eq!(
+            orbit(Symmetry::Rotational, 0, 1),
+            [Coordinate(0, 1), Coordinate(8, 7)]
+        );
+        assert_eq!(
+            orbit(Symmetry::QuarterRotational, 0, 1),
+            [
+                Coordinate(0, 1),
+                Coordinate(1, 8),
+                Coordinate(8, 7),
+                Coordinate(7, 0)
+            ]
+        );
+        assert_eq!(
+            orbit(Symmetry::AntiDiagonal, 0, 1),
+            [Coordinate(0, 1), Coordinate(7, 8)]
+        );
+        // cells on the axis are their own orbit
+        assert_eq!(orbit(Symmetry::Rotational, 4, 4), [Coordinate(4, 4)]);
+        assert_eq!(orbit(Symmetry::Diagonal, 3, 3), [Coordinate(3, 3)]);
+        assert_eq!(orbit(Symmetry::LeftRight, 2, 4), [Coordinate(2, 4)]);
+    }
+
+    #[test]
+    fn orbits_partition_the_grid() {
+        for symmetry in Symmetry::ALL {
+            for r in 0..9 {
+                for c in 0..9 {
+                    for cell in symmetry.orbit(Coordinate(r, c), 9) {
+                        assert!(symmetry.orbit(cell, 9).contains(&Coordinate(r, c)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for symmetry in Symmetry::ALL {
+            assert_eq!(symmetry.to_string().parse::<Symmetry>().unwrap(), symmetry);
+        }
+        assert_eq!(
+            "Left-Right".parse::<Symmetry>().unwrap(),
+            Symmetry::LeftRight
+        );
+        assert!("sideways".parse::<Symmetry>().is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use super::board::Board;
+use super::{grid_values, with_values, Solver, Uniqueness};
+use crate::model::Grid;
+
+/// depth-first search on the cell with the fewest candidates or value with the fewest places
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Backtracking;
+
+impl Solver for Backtracking {
+    fn solutions<const N: usize>(&self, grid: &Grid<N>, limit: usize) -> Vec<Grid<N>> {
+        match Search::<N>::new(&grid_values(grid), limit, true) {
+            Some(mut search) => {
+                search.run();
+                search
+                    .solutions
+                    .iter()
+                    .map(|values| with_values(grid, values))
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn count_solutions<const N: usize>(&self, grid: &Grid<N>, limit: usize) -> usize {
+        match Search::<N>::new(&grid_values(grid), limit, false) {
+            Some(mut search) => {
+                search.run();
+                search.count
+            }
+            None => 0,
+        }
+    }
+}
+
+impl Backtracking {
+    ///
+    /// Whether `grid` has a solution, giving up after visiting `nodes` search nodes.
+    /// `None` if the budget ran out before an answer. The nodes visited are taken off `nodes`.
+    ///
+    pub(crate) fn is_solvable_within<const N: usize>(
+        grid: &Grid<N>,
+        nodes: &mut usize,
+    ) -> Option<bool> {
+        match Search::<N>::new(&grid_values(grid), 1, false) {
+            Some(mut search) => {
+                search.nodes = *nodes;
+                search.run();
+                *nodes = search.nodes;
+                if search.count > 0 {
+                    Some(true)
+                } else if search.nodes == 0 {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            None => Some(false),
+        }
+    }
+
+    ///
+    /// Up to `limit` solutions of `grid`, giving up after visiting `nodes` search nodes.
+    /// `None` if the budget ran out before finding `limit` solutions or all there are.
+    ///
+    pub fn solutions_within<const N: usize>(
+        grid: &Grid<N>,
+        limit: usize,
+        nodes: usize,
+    ) -> Option<Vec<Grid<N>>> {
+        match Search::<N>::new(&grid_values(grid), limit, true) {
+            Some(mut search) => {
+                search.nodes = nodes;
+                search.run();
+                if search.count < limit && search.nodes == 0 {
+                    return None;
+                }
+                Some(
+                    search
+                        .solutions
+                        .iter()
+                        .map(|values| with_values(grid, values))
+                        .collect(),
+                )
+            }
+            None => Some(Vec::new()),
+        }
+    }
+
+    ///
+    /// Whether `grid` has no, one or several solutions, giving up after visiting `nodes`
+    /// search nodes. `None` if the budget ran out before an answer.
+    ///
+    pub fn uniqueness_within<const N: usize>(grid: &Grid<N>, nodes: usize) -> Option<Uniqueness> {
+        match Search::<N>::new(&grid_values(grid), 2, false) {
+            Some(mut search) => {
+                search.nodes = nodes;
+                search.run();
+                match search.count {
+                    0 | 1 if search.nodes == 0 => None,
+                    0 => Some(Uniqueness::None),
+                    1 => Some(Uniqueness::Unique),
+                    _ => Some(Uniqueness::Multiple),
+                }
+            }
+            None => Some(Uniqueness::None),
+        }
+    }
+}
+
+struct Search<const N: usize> {
+    board: Board<N>,
+    limit: usize,
+    collect: bool,
+    count: usize,
+    solutions: Vec<Vec<u8>>,
+    nodes: usize,           // search nodes left before giving up
+    units: Vec<Vec<usize>>, // cells of each row, column and sub grid
+    masks: Vec<u32>,        // candidates of each cell at the current node, 0 if filled
+}
+
+impl<const N: usize> Search<N> {
+    /// `None` if the given values already conflict
+    fn new(cells: &[Option<u8>], limit: usize, collect: bool) -> Option<Search<N>> {
+        let board: Board<N> = Board::new(cells)?;
+        Some(Search {
+            units: board.units(),
+            masks: vec![0; N * N],
+            board,
+            limit,
+            collect,
+            count: 0,
+            solutions: Vec::new(),
+            nodes: usize::MAX,
+        })
+    }
+
+    fn run(&mut self) {
+        if self.limit > 0 {
+            self.search();
+        }
+    }
+
+    /// returns true once `limit` solutions are found or the node budget runs out
+    fn search(&mut self) -> bool {
+        if self.nodes == 0 {
+            return true;
+        }
+        self.nodes -= 1;
+
+        // branch on the cell with the fewest candidates ...
+        let mut best: Option<(usize, u32)> = None;
+        let mut best_count = u32::MAX;
+        for i in 0..N * N {
+            if self.board.cells[i].is_some() {
+                self.masks[i] = 0;
+                continue;
+            }
+            let candidates = self.board.candidates(i);
+            self.masks[i] = candidates;
+            let count = candidates.count_ones();
+            if count == 0 {
+                return false;
+            }
+            if count < best_count {
+                best = Some((i, candidates));
+                best_count = count;
+                if count == 1 {
+                    break;
+                }
+            }
+        }
+
+        let (i, candidates) = match best {
+            Some(best) => best,
+            None => {
+                self.count += 1;
+                if self.collect {
+                    self.solutions
+                        .push(self.board.cells.iter().map(|v| v.unwrap()).collect());
+                }
+                return self.count >= self.limit;
+            }
+        };
+
+        let mut options: Vec<(usize, u8)> = (0..N as u8)
+            .filter(|value| candidates & (1 << value) != 0)
+            .map(|value| (i, value))
+            .collect();
+
+        // ... or on the value with the fewest places in a house, if fewer
+        // (every cell was visited above, so `masks` is up to date)
+        if options.len() > 1 {
+            for unit in &self.units {
+                let mut placed: u32 = 0;
+                let mut places = [0; N]; // empty cells of the unit with each candidate
+                for i in unit {
+                    match self.board.cells[*i] {
+                        Some(value) => placed |= 1 << value,
+                        None => {
+                            let mut mask = self.masks[*i];
+                            while mask != 0 {
+                                places[mask.trailing_zeros() as usize] += 1;
+                                mask &= mask - 1;
+                            }
+                        }
+                    }
+                }
+                for value in 0..N as u8 {
+                    if placed & (1 << value) != 0 {
+                        continue;
+                    }
+                    let bit = 1 << value;
+                    let places = places[value as usize];
+                    if places == 0 {
+                        return false;
+                    }
+                    if places < options.len() {
+                        options = unit
+                            .iter()
+                            .filter(|i| self.masks[**i] & bit != 0)
+                            .map(|i| (*i, value))
+                            .collect();
+                    }
+                }
+            }
+        }
+
+        for (i, value) in options {
+            self.board.place(i, value);
+            let done = self.search();
+            self.board.remove(i, value);
+            if done {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    #[test]
+    fn solves_and_counts() {
+        let grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        assert_eq!(
+            format_line(&Backtracking.solve(&grid).unwrap()).unwrap(),
+            SOLUTION
+        );
+        assert_eq!(Backtracking.uniqueness(&grid), Uniqueness::Unique);
+
+        let conflicting: Grid<9> = parse_line(&format!("55{}", &PUZZLE[2..])).unwrap();
+        assert_eq!(Backtracking.uniqueness(&conflicting), Uniqueness::None);
+
+        let empty: Grid<4> = parse_line(&".".repeat(16)).unwrap();
+        assert_eq!(Backtracking.count_solutions(&empty, 1000), 288);
+        assert_eq!(Backtracking.uniqueness(&empty), Uniqueness::Multiple);
+    }
+
+    #[test]
+    fn budgeted_searches_give_up() {
+        let puzzle: Grid<9> = parse_line(PUZZLE).unwrap();
+        let empty: Grid<9> = parse_line(&".".repeat(81)).unwrap();
+        let conflicting: Grid<9> = parse_line(&format!("55{}", &PUZZLE[2..])).unwrap();
+
+        assert_eq!(
+            Backtracking::uniqueness_within(&puzzle, 10_000),
+            Some(Uniqueness::Unique)
+        );
+        assert_eq!(
+            Backtracking::uniqueness_within(&empty, 10_000),
+            Some(Uniqueness::Multiple)
+        );
+        assert_eq!(
+            Backtracking::uniqueness_within(&conflicting, 0),
+            Some(Uniqueness::None)
+        );
+        // both solutions of an empty grid take a node per cell at least
+        assert_eq!(Backtracking::uniqueness_within(&empty, 50), None);
+
+        assert_eq!(
+            Backtracking::is_solvable_within(&puzzle, &mut 10_000),
+            Some(true)
+        );
+        assert_eq!(
+            Backtracking::is_solvable_within(&conflicting, &mut 0),
+            Some(false)
+        );
+        assert_eq!(Backtracking::is_solvable_within(&empty, &mut 50), None);
+
+        assert_eq!(
+            Backtracking::solutions_within(&empty, 2, 10_000).map(|solutions| solutions.len()),
+            Some(2)
+        );
+        assert_eq!(
+            Backtracking::solutions_within(&puzzle, 2, 10_000).map(|solutions| solutions.len()),
+            Some(1)
+        );
+        assert!(Backtracking::solutions_within(&empty, 2, 50).is_none());
+    }
+
+    #[test]
+    fn spent_nodes_are_taken_off_the_budget() {
+        let puzzle: Grid<9> = parse_line(PUZZLE).unwrap();
+        let mut nodes = 10_000;
+        assert_eq!(
+            Backtracking::is_solvable_within(&puzzle, &mut nodes),
+            Some(true)
+        );
+        assert!(nodes < 10_000);
+    }
+}
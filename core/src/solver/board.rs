@@ -0,0 +1,76 @@
+/// cell values with bit masks of values used in each row, column and sub grid
+#[derive(Debug, Clone)]
+pub(crate) struct Board<const N: usize> {
+    pub cells: Vec<Option<u8>>, // row by row
+    rows: Vec<u32>,
+    cols: Vec<u32>,
+    boxes: Vec<u32>,
+    sub_grid_size: usize,
+}
+
+impl<const N: usize> Board<N> {
+    pub fn empty() -> Board<N> {
+        Board {
+            cells: vec![None; N * N],
+            rows: vec![0; N],
+            cols: vec![0; N],
+            boxes: vec![0; N],
+            sub_grid_size: (N as f64).sqrt() as usize,
+        }
+    }
+
+    /// `None` if the values conflict
+    pub fn new(cells: &[Option<u8>]) -> Option<Board<N>> {
+        let mut board = Board::empty();
+        for (i, value) in cells.iter().enumerate() {
+            if let Some(value) = value {
+                if board.candidates(i) & (1 << value) == 0 {
+                    return None;
+                }
+                board.place(i, *value);
+            }
+        }
+        Some(board)
+    }
+
+    pub fn box_index(&self, i: usize) -> usize {
+        let (row, col) = (i / N, i % N);
+        (row / self.sub_grid_size) * self.sub_grid_size + col / self.sub_grid_size
+    }
+
+    /// indices of the cells in each row, column and sub grid
+    pub fn units(&self) -> Vec<Vec<usize>> {
+        let mut units: Vec<Vec<usize>> = Vec::new();
+        for i in 0..N {
+            units.push((0..N).map(|j| i * N + j).collect());
+            units.push((0..N).map(|j| j * N + i).collect());
+            units.push((0..N * N).filter(|j| self.box_index(*j) == i).collect());
+        }
+        units
+    }
+
+    /// bit mask of values that can be placed at cell `i`
+    pub fn candidates(&self, i: usize) -> u32 {
+        let full: u32 = if N >= 32 { u32::MAX } else { (1 << N) - 1 };
+        let used = self.rows[i / N] | self.cols[i % N] | self.boxes[self.box_index(i)];
+        full & !used
+    }
+
+    pub fn place(&mut self, i: usize, value: u8) {
+        let bit = 1 << value;
+        let b = self.box_index(i);
+        self.cells[i] = Some(value);
+        self.rows[i / N] |= bit;
+        self.cols[i % N] |= bit;
+        self.boxes[b] |= bit;
+    }
+
+    pub fn remove(&mut self, i: usize, value: u8) {
+        let bit = !(1 << value);
+        let b = self.box_index(i);
+        self.cells[i] = None;
+        self.rows[i / N] &= bit;
+        self.cols[i % N] &= bit;
+        self.boxes[b] &= bit;
+    }
+}
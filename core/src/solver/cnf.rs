@@ -0,0 +1,141 @@
+use super::board::Board;
+use super::grid_values;
+use crate::model::Grid;
+
+///
+/// Boolean formula in conjunctive normal form encoding a grid and its constraints.
+///
+/// Variable `row * N * N + col * N + value + 1` is true when the cell at
+/// (`row`, `col`) holds `value`, literals are DIMACS style (negative when negated).
+///
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cnf {
+    pub variables: usize,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+impl Cnf {
+    pub fn from_grid<const N: usize>(grid: &Grid<N>) -> Cnf {
+        let board: Board<N> = Board::empty();
+        let variable = |i: usize, value: usize| (i * N + value + 1) as i32;
+        let mut clauses: Vec<Vec<i32>> = Vec::new();
+
+        // each cell holds exactly one value
+        for i in 0..N * N {
+            let literals: Vec<i32> = (0..N).map(|value| variable(i, value)).collect();
+            exactly_one(&mut clauses, &literals);
+        }
+
+        // each value appears exactly once in each row, column and sub grid
+        for unit in board.units() {
+            for value in 0..N {
+                let literals: Vec<i32> = unit.iter().map(|i| variable(*i, value)).collect();
+                exactly_one(&mut clauses, &literals);
+            }
+        }
+
+        for (i, value) in grid_values(grid).iter().enumerate() {
+            if let Some(value) = value {
+                clauses.push(vec![variable(i, *value as usize)]);
+            }
+        }
+
+        Cnf {
+            variables: N * N * N,
+            clauses,
+        }
+    }
+
+    /// cell values (row by row) from an assignment of the variables, index 0 is variable 1
+    pub fn decode<const N: usize>(&self, assignment: &[bool]) -> Vec<u8> {
+        (0..N * N)
+            .map(|i| {
+                (0..N)
+                    .find(|value| assignment[i * N + value])
+                    .unwrap_or_default() as u8
+            })
+            .collect()
+    }
+
+    pub fn to_dimacs(&self) -> String {
+        let mut result = String::from("c sudokube: variable row * N * N + col * N + value + 1\n");
+        result.push_str(&format!(
+            "p cnf {} {}\n",
+            self.variables,
+            self.clauses.len()
+        ));
+        for clause in &self.clauses {
+            for literal in clause {
+                result.push_str(&literal.to_string());
+                result.push(' ');
+            }
+            result.push_str("0\n");
+        }
+        result
+    }
+}
+
+fn exactly_one(clauses: &mut Vec<Vec<i32>>, literals: &[i32]) {
+    clauses.push(literals.to_vec());
+    for (i, a) in literals.iter().enumerate() {
+        for b in &literals[i + 1..] {
+            clauses.push(vec![-a, -b]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parse_line;
+
+    const PUZZLE: &str = "12....1.......21";
+    const SOLUTION: &str = "1234341221434321";
+
+    /// assignment of the variables setting each cell to its value in `line`
+    fn assignment(line: &str) -> Vec<bool> {
+        let values: Vec<usize> = line.bytes().map(|b| (b - b'1') as usize).collect();
+        (0..64).map(|v| values[v / 4] == v % 4).collect()
+    }
+
+    fn satisfies(cnf: &Cnf, assignment: &[bool]) -> bool {
+        cnf.clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|literal| assignment[literal.unsigned_abs() as usize - 1] == (*literal > 0))
+        })
+    }
+
+    #[test]
+    fn encodes_cells_units_and_givens() {
+        let cnf = Cnf::from_grid(&parse_line::<4>(PUZZLE).unwrap());
+        assert_eq!(cnf.variables, 64);
+        // at least one and pairwise at most one, for 16 cells and 4 values in 12 units
+        assert_eq!(cnf.clauses.len(), (16 + 12 * 4) * (1 + 6) + 5);
+        assert!(cnf.clauses.contains(&vec![1]));
+        assert!(cnf.clauses.contains(&vec![6 * 4 + 1]));
+
+        assert!(satisfies(&cnf, &assignment(SOLUTION)));
+        assert_eq!(
+            cnf.decode::<4>(&assignment(SOLUTION)),
+            [0, 1, 2, 3, 2, 3, 0, 1, 1, 0, 3, 2, 3, 2, 1, 0]
+        );
+        // swapping two values of a row breaks the columns
+        assert!(!satisfies(&cnf, &assignment("2134341212434321")));
+        // a complete grid ignoring the givens, 1 and 2 swapped
+        assert!(!satisfies(&cnf, &assignment("2134342112434312")));
+    }
+
+    #[test]
+    fn writes_dimacs() {
+        let cnf = Cnf::from_grid(&parse_line::<4>(PUZZLE).unwrap());
+        let dimacs = cnf.to_dimacs();
+        let lines: Vec<&str> = dimacs.lines().collect();
+        assert!(lines[0].starts_with("c "));
+        assert_eq!(lines[1], format!("p cnf 64 {}", cnf.clauses.len()));
+        assert_eq!(lines.len(), 2 + cnf.clauses.len());
+        assert_eq!(lines[2], "1 2 3 4 0");
+        assert_eq!(lines[3], "-1 -2 0");
+        assert_eq!(*lines.last().unwrap(), "61 0");
+    }
+}
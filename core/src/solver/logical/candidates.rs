@@ -0,0 +1,126 @@
+use crate::model::{Coordinate, Grid};
+use crate::solver::board::Board;
+use crate::solver::grid_values;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Cell values and the candidates left in each empty cell
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Candidates<const N: usize> {
+    pub(super) values: Vec<Option<u8>>, // row by row
+    pub(super) masks: Vec<u32>,         // bit `v` set if value `v` is a candidate
+}
+
+impl<const N: usize> Candidates<N> {
+    /// every value not used by a peer is a candidate of an empty cell
+    pub fn from_grid(grid: &Grid<N>) -> Candidates<N> {
+        let values = grid_values(grid);
+        let mut board: Board<N> = Board::empty();
+        for (i, value) in values.iter().enumerate() {
+            if let Some(value) = value {
+                board.place(i, *value);
+            }
+        }
+
+        let masks = (0..N * N)
+            .map(|i| match values[i] {
+                Some(_) => 0,
+                None => board.candidates(i),
+            })
+            .collect();
+
+        Candidates { values, masks }
+    }
+
+    pub fn value(&self, coordinate: Coordinate) -> Option<u8> {
+        self.values[index::<N>(coordinate)]
+    }
+
+    pub fn get(&self, coordinate: Coordinate) -> Vec<u8> {
+        let mask = self.masks[index::<N>(coordinate)];
+        (0..N as u8)
+            .filter(|value| mask & (1 << value) != 0)
+            .collect()
+    }
+
+    pub fn has(&self, coordinate: Coordinate, value: u8) -> bool {
+        self.masks[index::<N>(coordinate)] & (1 << value) != 0
+    }
+
+    /// keep only candidates in `mask`, e.g. to respect the player's notes
+    pub fn restrict(&mut self, coordinate: Coordinate, mask: u32) {
+        self.masks[index::<N>(coordinate)] &= mask;
+    }
+
+    pub fn eliminate(&mut self, coordinate: Coordinate, value: u8) {
+        self.masks[index::<N>(coordinate)] &= !(1 << value);
+    }
+
+    /// set the value of a cell and remove it from the candidates of its peers
+    pub fn place(&mut self, coordinate: Coordinate, value: u8) {
+        let i = index::<N>(coordinate);
+        self.values[i] = Some(value);
+        self.masks[i] = 0;
+        for peer in &peers::<N>()[i] {
+            self.masks[*peer] &= !(1 << value);
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.values.iter().all(|value| value.is_some())
+    }
+
+    /// empty cells without candidates
+    pub fn dead_ends(&self) -> Vec<Coordinate> {
+        (0..N * N)
+            .filter(|i| self.values[*i].is_none() && self.masks[*i] == 0)
+            .map(coordinate::<N>)
+            .collect()
+    }
+
+    /// copy of `grid` with the placed values filled in
+    pub fn to_grid(&self, grid: &Grid<N>) -> Grid<N> {
+        let mut result = grid.clone();
+        for (i, value) in self.values.iter().enumerate() {
+            let coordinate = coordinate::<N>(i);
+            if result.get_cell(coordinate).value != *value {
+                result.set_cell_value(coordinate, *value).unwrap();
+            }
+        }
+        result
+    }
+}
+
+pub(super) fn index<const N: usize>(coordinate: Coordinate) -> usize {
+    let Coordinate(row, col) = coordinate;
+    row as usize * N + col as usize
+}
+
+pub(super) fn coordinate<const N: usize>(i: usize) -> Coordinate {
+    Coordinate((i / N) as u8, (i % N) as u8)
+}
+
+/// other cells sharing a row, column or sub grid with each cell, by cell index
+type Peers = &'static [Vec<usize>];
+
+/// peer table of grids of size `N`, built once per size
+fn peers<const N: usize>() -> Peers {
+    static PEERS: OnceLock<Mutex<HashMap<usize, Peers>>> = OnceLock::new();
+    let mut tables = PEERS.get_or_init(Default::default).lock().unwrap();
+    tables.entry(N).or_insert_with(|| {
+        let board: Board<N> = Board::empty();
+        let table: Vec<Vec<usize>> = (0..N * N)
+            .map(|i| {
+                (0..N * N)
+                    .filter(|j| {
+                        *j != i
+                            && (j / N == i / N
+                                || j % N == i % N
+                                || board.box_index(*j) == board.box_index(i))
+                    })
+                    .collect()
+            })
+            .collect();
+        Box::leak(table.into_boxed_slice())
+    })
+}
@@ -0,0 +1,313 @@
+mod candidates;
+mod techniques;
+
+pub use candidates::Candidates;
+
+use crate::model::{Coordinate, Difficulty, Grid};
+use candidates::{coordinate, index};
+
+/// Human solving technique
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Technique {
+    NakedSingle,  // only one candidate left in a cell
+    HiddenSingle, // only one place left for a value in a house
+    Pointing,     // candidates of a value in a sub grid are all in one row / column
+    Claiming,     // candidates of a value in a row / column are all in one sub grid
+    NakedPair,    // two cells of a house with the same two candidates
+    HiddenPair,   // two values of a house with the same two places
+    NakedTriple,  // three cells of a house with three candidates between them
+    HiddenTriple, // three values of a house with three places between them
+    XWing,        // candidates of a value in two rows are in the same two columns, or vice versa
+    XYWing,       // cell with two candidates and two pincers sharing one each and a third
+    Swordfish,    // x-wing with three rows and three columns
+}
+
+impl Technique {
+    pub fn difficulty(&self) -> Difficulty {
+        match self {
+            Technique::NakedSingle | Technique::HiddenSingle => Difficulty::Easy,
+            Technique::Pointing
+            | Technique::Claiming
+            | Technique::NakedPair
+            | Technique::HiddenPair => Difficulty::Medium,
+            Technique::NakedTriple
+            | Technique::HiddenTriple
+            | Technique::XWing
+            | Technique::XYWing
+            | Technique::Swordfish => Difficulty::Hard,
+        }
+    }
+}
+
+impl std::fmt::Display for Technique {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Technique::NakedSingle => "Naked Single",
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::Pointing => "Pointing",
+            Technique::Claiming => "Claiming",
+            Technique::NakedPair => "Naked Pair",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::XWing => "X-Wing",
+            Technique::XYWing => "XY-Wing",
+            Technique::Swordfish => "Swordfish",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Row, column or sub grid, by index
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum House {
+    Row(u8),
+    Col(u8),
+    SubGrid(u8), // row by row
+}
+
+impl House {
+    /// every house of a grid of size `N`
+    pub fn all<const N: usize>() -> Vec<House> {
+        (0..N as u8)
+            .map(House::Row)
+            .chain((0..N as u8).map(House::Col))
+            .chain((0..N as u8).map(House::SubGrid))
+            .collect()
+    }
+
+    pub fn coordinates<const N: usize>(&self) -> Vec<Coordinate> {
+        let size = (N as f64).sqrt() as u8;
+        (0..N as u8)
+            .map(|i| match self {
+                House::Row(row) => Coordinate(*row, i),
+                House::Col(col) => Coordinate(i, *col),
+                House::SubGrid(b) => {
+                    Coordinate(b / size * size + i / size, b % size * size + i % size)
+                }
+            })
+            .collect()
+    }
+
+    fn indices<const N: usize>(&self) -> Vec<usize> {
+        self.coordinates::<N>()
+            .into_iter()
+            .map(index::<N>)
+            .collect()
+    }
+}
+
+impl std::fmt::Display for House {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            House::Row(row) => write!(f, "row {}", row + 1),
+            House::Col(col) => write!(f, "column {}", col + 1),
+            House::SubGrid(b) => write!(f, "box {}", b + 1),
+        }
+    }
+}
+
+/// One deduction: placements and candidate eliminations justified by a technique
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Step {
+    pub technique: Technique,
+    pub houses: Vec<House>,     // where to look
+    pub cells: Vec<Coordinate>, // cells forming the pattern
+    pub values: Vec<u8>,        // candidates forming the pattern
+    pub placements: Vec<(Coordinate, u8)>,
+    pub eliminations: Vec<(Coordinate, u8)>,
+}
+
+impl<const N: usize> Candidates<N> {
+    pub fn apply(&mut self, step: &Step) {
+        for (coordinate, value) in &step.placements {
+            self.place(*coordinate, *value);
+        }
+        for (coordinate, value) in &step.eliminations {
+            self.eliminate(*coordinate, *value);
+        }
+    }
+}
+
+/// Steps taken by `solve_logically` and whether they solved the grid
+pub struct LogicalSolution<const N: usize> {
+    pub candidates: Candidates<N>,
+    pub steps: Vec<Step>,
+}
+
+impl<const N: usize> LogicalSolution<N> {
+    pub fn is_solved(&self) -> bool {
+        self.candidates.is_solved()
+    }
+
+    /// difficulty of the hardest technique used, `Expert` if the grid was not solved
+    pub fn difficulty(&self) -> Difficulty {
+        if !self.is_solved() {
+            return Difficulty::Expert;
+        }
+        self.steps
+            .iter()
+            .map(|step| step.technique.difficulty())
+            .max()
+            .unwrap_or(Difficulty::Easy)
+    }
+}
+
+/// easiest step that can be taken from `candidates`, `None` if stuck or solved
+pub fn next_step<const N: usize>(candidates: &Candidates<N>) -> Option<Step> {
+    if !candidates.dead_ends().is_empty() {
+        return None;
+    }
+    techniques::find_step(candidates)
+}
+
+/// apply the easiest step until the grid is solved or no technique applies
+pub fn solve_logically<const N: usize>(grid: &Grid<N>) -> LogicalSolution<N> {
+    let mut candidates = Candidates::from_grid(grid);
+    let mut steps: Vec<Step> = Vec::new();
+    while let Some(step) = next_step(&candidates) {
+        candidates.apply(&step);
+        steps.push(step);
+    }
+    LogicalSolution { candidates, steps }
+}
+
+fn coordinates<const N: usize>(indices: &[usize]) -> Vec<Coordinate> {
+    indices.iter().map(|i| coordinate::<N>(*i)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parse_line;
+    use crate::solver::{Backtracking, Solver};
+    use std::collections::HashSet;
+
+    const EASY: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const HARD: [&str; 4] = [
+        "........5.5.4.96.82..3..49....931....61...........5..2.2.7..3..3...148.7........9",
+        ".8..........2.8.352..16...95....3..8...51.37.......6...7.....9.1...7..6.63..9.52.",
+        ".....875....6.29.3.6...3.2....57....3.....6..71....8....3..5.4..74........9.2....",
+        "8249........8.....31...6..2..5..7.......5.9..16..2..4.....68..75..2..83...17..2.4",
+    ];
+
+    /// candidates of an empty grid with `value` kept only at `cells` in row `row`
+    fn only_in_row(candidates: &mut Candidates<9>, row: u8, cells: &[u8], value: u8) {
+        for col in (0..9).filter(|col| !cells.contains(col)) {
+            candidates.eliminate(Coordinate(row, col), value);
+        }
+    }
+
+    #[test]
+    fn steps_agree_with_the_solution() {
+        let mut used: HashSet<Technique> = HashSet::new();
+        for puzzle in [EASY].iter().chain(HARD.iter()) {
+            let grid: Grid<9> = parse_line(puzzle).unwrap();
+            let solution = Backtracking.solve(&grid).unwrap();
+            let logical = solve_logically(&grid);
+            assert!(logical.is_solved(), "{}", puzzle);
+
+            for step in &logical.steps {
+                used.insert(step.technique);
+                for (coordinate, value) in &step.placements {
+                    assert_eq!(
+                        solution.get_cell(*coordinate).value,
+                        Some(*value),
+                        "{:?}",
+                        step
+                    );
+                }
+                for (coordinate, value) in &step.eliminations {
+                    assert_ne!(
+                        solution.get_cell(*coordinate).value,
+                        Some(*value),
+                        "{:?}",
+                        step
+                    );
+                }
+            }
+            for row in &solution.rows {
+                for cell in &row.cells {
+                    assert_eq!(logical.candidates.value(cell.coordinate), cell.value);
+                }
+            }
+        }
+
+        let expected = [
+            Technique::NakedSingle,
+            Technique::HiddenSingle,
+            Technique::Pointing,
+            Technique::Claiming,
+            Technique::NakedPair,
+            Technique::HiddenPair,
+            Technique::NakedTriple,
+            Technique::XWing,
+            Technique::XYWing,
+        ];
+        for technique in expected {
+            assert!(used.contains(&technique), "{} not used", technique);
+        }
+    }
+
+    #[test]
+    fn difficulty_is_the_hardest_technique() {
+        let easy = solve_logically(&parse_line::<9>(EASY).unwrap());
+        assert_eq!(easy.difficulty(), Difficulty::Easy);
+        for puzzle in HARD {
+            let hard = solve_logically(&parse_line::<9>(puzzle).unwrap());
+            assert_eq!(hard.difficulty(), Difficulty::Hard, "{}", puzzle);
+        }
+
+        // no technique applies to an empty grid
+        let empty = solve_logically(&parse_line::<9>(&".".repeat(81)).unwrap());
+        assert!(empty.steps.is_empty());
+        assert!(!empty.is_solved());
+        assert_eq!(empty.difficulty(), Difficulty::Expert);
+    }
+
+    #[test]
+    fn no_step_from_a_dead_end() {
+        // r1c1 has no candidate left
+        let line = format!(".12345678{}9{}", ".".repeat(18), ".".repeat(53));
+        let candidates = Candidates::from_grid(&parse_line::<9>(&line).unwrap());
+        assert_eq!(candidates.dead_ends(), [Coordinate(0, 0)]);
+        assert_eq!(next_step(&candidates), None);
+    }
+
+    #[test]
+    fn finds_swordfish() {
+        let mut candidates = Candidates::from_grid(&parse_line::<9>(&".".repeat(81)).unwrap());
+        for row in [0, 3, 6] {
+            only_in_row(&mut candidates, row, &[0, 3, 6], 0);
+        }
+
+        let step = next_step(&candidates).unwrap();
+        assert_eq!(step.technique, Technique::Swordfish);
+        assert_eq!(step.houses, [House::Row(0), House::Row(3), House::Row(6)]);
+        assert_eq!(step.values, [0]);
+        assert_eq!(step.eliminations.len(), 18);
+        for (Coordinate(row, col), value) in step.eliminations {
+            assert!(![0, 3, 6].contains(&row) && [0, 3, 6].contains(&col) && value == 0);
+        }
+    }
+
+    #[test]
+    fn finds_hidden_triple() {
+        let mut candidates = Candidates::from_grid(&parse_line::<9>(&".".repeat(81)).unwrap());
+        for value in 0..3 {
+            only_in_row(&mut candidates, 0, &[0, 3, 6], value);
+        }
+
+        let step = next_step(&candidates).unwrap();
+        assert_eq!(step.technique, Technique::HiddenTriple);
+        assert_eq!(step.houses, [House::Row(0)]);
+        assert_eq!(
+            step.cells,
+            [Coordinate(0, 0), Coordinate(0, 3), Coordinate(0, 6)]
+        );
+        assert_eq!(step.values, [0, 1, 2]);
+        assert_eq!(step.eliminations.len(), 3 * 6);
+        assert!(step.eliminations.iter().all(|(_, value)| *value >= 3));
+    }
+}
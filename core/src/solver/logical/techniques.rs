@@ -0,0 +1,421 @@
+use super::candidates::{coordinate, Candidates};
+use super::{coordinates, House, Step, Technique};
+use crate::model::Coordinate;
+
+/// easiest technique first, see `Technique::difficulty`
+pub(super) fn find_step<const N: usize>(candidates: &Candidates<N>) -> Option<Step> {
+    // sub grids first, that is where people look for hidden singles
+    let mut houses = House::all::<N>();
+    houses.rotate_left(2 * N);
+
+    naked_single(candidates)
+        .or_else(|| hidden_single(candidates, &houses))
+        .or_else(|| pointing(candidates))
+        .or_else(|| claiming(candidates))
+        .or_else(|| naked_subset(candidates, &houses, 2))
+        .or_else(|| hidden_subset(candidates, &houses, 2))
+        .or_else(|| naked_subset(candidates, &houses, 3))
+        .or_else(|| hidden_subset(candidates, &houses, 3))
+        .or_else(|| fish(candidates, 2))
+        .or_else(|| xy_wing(candidates))
+        .or_else(|| fish(candidates, 3))
+}
+
+fn naked_single<const N: usize>(candidates: &Candidates<N>) -> Option<Step> {
+    let i = (0..N * N)
+        .find(|i| candidates.values[*i].is_none() && candidates.masks[*i].count_ones() == 1)?;
+    let value = candidates.masks[i].trailing_zeros() as u8;
+    let cell = coordinate::<N>(i);
+    Some(Step {
+        technique: Technique::NakedSingle,
+        houses: vec![
+            House::Row(cell.row()),
+            House::Col(cell.col()),
+            House::SubGrid(sub_grid_of::<N>(i)),
+        ],
+        cells: vec![cell],
+        values: vec![value],
+        placements: vec![(cell, value)],
+        eliminations: Vec::new(),
+    })
+}
+
+fn hidden_single<const N: usize>(candidates: &Candidates<N>, houses: &[House]) -> Option<Step> {
+    for house in houses {
+        let cells = house.indices::<N>();
+        for value in 0..N as u8 {
+            let places = places(candidates, &cells, value);
+            if places.len() == 1 && !placed(candidates, &cells, value) {
+                let cell = coordinate::<N>(places[0]);
+                return Some(Step {
+                    technique: Technique::HiddenSingle,
+                    houses: vec![*house],
+                    cells: vec![cell],
+                    values: vec![value],
+                    placements: vec![(cell, value)],
+                    eliminations: Vec::new(),
+                });
+            }
+        }
+    }
+    None
+}
+
+fn pointing<const N: usize>(candidates: &Candidates<N>) -> Option<Step> {
+    for b in 0..N as u8 {
+        let sub_grid = House::SubGrid(b).indices::<N>();
+        for value in 0..N as u8 {
+            let places = places(candidates, &sub_grid, value);
+            if places.len() < 2 {
+                continue;
+            }
+
+            let row = places[0] / N;
+            let col = places[0] % N;
+            let lines = [
+                (places.iter().all(|i| i / N == row), House::Row(row as u8)),
+                (places.iter().all(|i| i % N == col), House::Col(col as u8)),
+            ];
+            for (aligned, line) in lines {
+                if !aligned {
+                    continue;
+                }
+                let outside: Vec<usize> = line
+                    .indices::<N>()
+                    .into_iter()
+                    .filter(|i| !sub_grid.contains(i))
+                    .collect();
+                let eliminations = eliminations::<N>(candidates, &outside, value);
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::Pointing,
+                        houses: vec![House::SubGrid(b), line],
+                        cells: coordinates::<N>(&places),
+                        values: vec![value],
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+fn claiming<const N: usize>(candidates: &Candidates<N>) -> Option<Step> {
+    let lines = (0..N as u8)
+        .map(House::Row)
+        .chain((0..N as u8).map(House::Col));
+    for line in lines {
+        let cells = line.indices::<N>();
+        for value in 0..N as u8 {
+            let places = places(candidates, &cells, value);
+            if places.len() < 2 {
+                continue;
+            }
+
+            let b = sub_grid_of::<N>(places[0]);
+            if places.iter().any(|i| sub_grid_of::<N>(*i) != b) {
+                continue;
+            }
+            let outside: Vec<usize> = House::SubGrid(b)
+                .indices::<N>()
+                .into_iter()
+                .filter(|i| !cells.contains(i))
+                .collect();
+            let eliminations = eliminations::<N>(candidates, &outside, value);
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: Technique::Claiming,
+                    houses: vec![line, House::SubGrid(b)],
+                    cells: coordinates::<N>(&places),
+                    values: vec![value],
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// `size` cells of a house with `size` candidates between them
+fn naked_subset<const N: usize>(
+    candidates: &Candidates<N>,
+    houses: &[House],
+    size: usize,
+) -> Option<Step> {
+    let technique = match size {
+        2 => Technique::NakedPair,
+        _ => Technique::NakedTriple,
+    };
+
+    for house in houses {
+        let empty: Vec<usize> = house
+            .indices::<N>()
+            .into_iter()
+            .filter(|i| candidates.values[*i].is_none())
+            .collect();
+        let small: Vec<usize> = empty
+            .iter()
+            .copied()
+            .filter(|i| (2..=size as u32).contains(&candidates.masks[*i].count_ones()))
+            .collect();
+
+        for subset in combinations(&small, size) {
+            let union = subset
+                .iter()
+                .fold(0, |union, i| union | candidates.masks[*i]);
+            if union.count_ones() as usize != size {
+                continue;
+            }
+
+            let mut eliminations = Vec::new();
+            for i in empty.iter().filter(|i| !subset.contains(i)) {
+                for value in values_of::<N>(candidates.masks[*i] & union) {
+                    eliminations.push((coordinate::<N>(*i), value));
+                }
+            }
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique,
+                    houses: vec![*house],
+                    cells: coordinates::<N>(&subset),
+                    values: values_of::<N>(union),
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// `size` values of a house with `size` places between them
+fn hidden_subset<const N: usize>(
+    candidates: &Candidates<N>,
+    houses: &[House],
+    size: usize,
+) -> Option<Step> {
+    let technique = match size {
+        2 => Technique::HiddenPair,
+        _ => Technique::HiddenTriple,
+    };
+
+    for house in houses {
+        let cells = house.indices::<N>();
+        let values: Vec<usize> = (0..N)
+            .filter(|value| {
+                let count = places(candidates, &cells, *value as u8).len();
+                (2..=size).contains(&count)
+            })
+            .collect();
+
+        for subset in combinations(&values, size) {
+            let mut union: Vec<usize> = Vec::new();
+            for value in &subset {
+                for i in places(candidates, &cells, *value as u8) {
+                    if !union.contains(&i) {
+                        union.push(i);
+                    }
+                }
+            }
+            if union.len() != size {
+                continue;
+            }
+            union.sort_unstable();
+
+            let mask = subset.iter().fold(0u32, |mask, value| mask | (1 << value));
+            let mut eliminations = Vec::new();
+            for i in &union {
+                for value in values_of::<N>(candidates.masks[*i] & !mask) {
+                    eliminations.push((coordinate::<N>(*i), value));
+                }
+            }
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique,
+                    houses: vec![*house],
+                    cells: coordinates::<N>(&union),
+                    values: subset.iter().map(|value| *value as u8).collect(),
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// candidates of a value in `size` rows lie in `size` columns (or vice versa)
+fn fish<const N: usize>(candidates: &Candidates<N>, size: usize) -> Option<Step> {
+    let technique = match size {
+        2 => Technique::XWing,
+        _ => Technique::Swordfish,
+    };
+
+    for value in 0..N as u8 {
+        for by_rows in [true, false] {
+            let line = |i: usize| match by_rows {
+                true => House::Row(i as u8),
+                false => House::Col(i as u8),
+            };
+            let cross = |i: usize| match by_rows {
+                true => House::Col(i as u8),
+                false => House::Row(i as u8),
+            };
+            // position of a cell along a line
+            let position = |i: usize| match by_rows {
+                true => i % N,
+                false => i / N,
+            };
+
+            let lines: Vec<usize> = (0..N)
+                .filter(|l| {
+                    let count = places(candidates, &line(*l).indices::<N>(), value).len();
+                    (2..=size).contains(&count)
+                })
+                .collect();
+
+            for subset in combinations(&lines, size) {
+                let corners: Vec<usize> = subset
+                    .iter()
+                    .flat_map(|l| places(candidates, &line(*l).indices::<N>(), value))
+                    .collect();
+                let mut crosses: Vec<usize> = corners.iter().map(|i| position(*i)).collect();
+                crosses.sort_unstable();
+                crosses.dedup();
+                if crosses.len() != size {
+                    continue;
+                }
+
+                let outside: Vec<usize> = crosses
+                    .iter()
+                    .flat_map(|c| cross(*c).indices::<N>())
+                    .filter(|i| !corners.contains(i))
+                    .collect();
+                let eliminations = eliminations::<N>(candidates, &outside, value);
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique,
+                        houses: subset.iter().map(|l| line(*l)).collect(),
+                        cells: coordinates::<N>(&corners),
+                        values: vec![value],
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+///
+/// A pivot cell with candidates xy sees two pincers with xz and yz:
+/// z is eliminated from cells seeing both pincers.
+///
+fn xy_wing<const N: usize>(candidates: &Candidates<N>) -> Option<Step> {
+    let pairs: Vec<usize> = (0..N * N)
+        .filter(|i| candidates.values[*i].is_none() && candidates.masks[*i].count_ones() == 2)
+        .collect();
+
+    for pivot in &pairs {
+        let pivot_mask = candidates.masks[*pivot];
+        let pincers: Vec<usize> = pairs
+            .iter()
+            .copied()
+            .filter(|i| {
+                let shared = candidates.masks[*i] & pivot_mask;
+                sees::<N>(*pivot, *i) && shared.count_ones() == 1
+            })
+            .collect();
+
+        for (a, pincer_a) in pincers.iter().enumerate() {
+            for pincer_b in &pincers[a + 1..] {
+                let mask_a = candidates.masks[*pincer_a];
+                let mask_b = candidates.masks[*pincer_b];
+                let z = mask_a & mask_b & !pivot_mask;
+                if z.count_ones() != 1 || (mask_a | mask_b) & pivot_mask != pivot_mask {
+                    continue;
+                }
+
+                let value = z.trailing_zeros() as u8;
+                let seeing_both: Vec<usize> = (0..N * N)
+                    .filter(|i| {
+                        i != pincer_a
+                            && i != pincer_b
+                            && sees::<N>(*i, *pincer_a)
+                            && sees::<N>(*i, *pincer_b)
+                    })
+                    .collect();
+                let eliminations = eliminations::<N>(candidates, &seeing_both, value);
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::XYWing,
+                        houses: Vec::new(),
+                        cells: coordinates::<N>(&[*pivot, *pincer_a, *pincer_b]),
+                        values: values_of::<N>(pivot_mask | z),
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// empty cells among `cells` with `value` as a candidate
+fn places<const N: usize>(candidates: &Candidates<N>, cells: &[usize], value: u8) -> Vec<usize> {
+    cells
+        .iter()
+        .copied()
+        .filter(|i| candidates.values[*i].is_none() && candidates.masks[*i] & (1 << value) != 0)
+        .collect()
+}
+
+fn placed<const N: usize>(candidates: &Candidates<N>, cells: &[usize], value: u8) -> bool {
+    cells.iter().any(|i| candidates.values[*i] == Some(value))
+}
+
+fn eliminations<const N: usize>(
+    candidates: &Candidates<N>,
+    cells: &[usize],
+    value: u8,
+) -> Vec<(Coordinate, u8)> {
+    places(candidates, cells, value)
+        .into_iter()
+        .map(|i| (coordinate::<N>(i), value))
+        .collect()
+}
+
+fn values_of<const N: usize>(mask: u32) -> Vec<u8> {
+    (0..N as u8)
+        .filter(|value| mask & (1 << value) != 0)
+        .collect()
+}
+
+fn sub_grid_of<const N: usize>(i: usize) -> u8 {
+    let size = (N as f64).sqrt() as usize;
+    ((i / N) / size * size + (i % N) / size) as u8
+}
+
+/// whether two different cells share a row, column or sub grid
+fn sees<const N: usize>(a: usize, b: usize) -> bool {
+    a != b && (a / N == b / N || a % N == b % N || sub_grid_of::<N>(a) == sub_grid_of::<N>(b))
+}
+
+fn combinations(items: &[usize], size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], size - 1) {
+            rest.insert(0, *item);
+            result.push(rest);
+        }
+    }
+    result
+}
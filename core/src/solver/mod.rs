@@ -0,0 +1,63 @@
+mod backtracking;
+pub(crate) mod board;
+mod cnf;
+pub mod logical;
+#[cfg(feature = "sat")]
+mod sat;
+
+pub use backtracking::Backtracking;
+pub use cnf::Cnf;
+#[cfg(feature = "sat")]
+pub use sat::Sat;
+
+use crate::model::{Coordinate, Grid};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Uniqueness {
+    None,     // no solution
+    Unique,   // exactly one solution
+    Multiple, // more than one solution
+}
+
+pub trait Solver {
+    /// up to `limit` solutions of `grid`, cells keep their editable flag
+    fn solutions<const N: usize>(&self, grid: &Grid<N>, limit: usize) -> Vec<Grid<N>>;
+
+    fn solve<const N: usize>(&self, grid: &Grid<N>) -> Option<Grid<N>> {
+        self.solutions(grid, 1).into_iter().next()
+    }
+
+    /// number of solutions of `grid`, counting stops at `limit`
+    fn count_solutions<const N: usize>(&self, grid: &Grid<N>, limit: usize) -> usize {
+        self.solutions(grid, limit).len()
+    }
+
+    fn uniqueness<const N: usize>(&self, grid: &Grid<N>) -> Uniqueness {
+        match self.count_solutions(grid, 2) {
+            0 => Uniqueness::None,
+            1 => Uniqueness::Unique,
+            _ => Uniqueness::Multiple,
+        }
+    }
+}
+
+/// cell values of `grid`, row by row
+pub(crate) fn grid_values<const N: usize>(grid: &Grid<N>) -> Vec<Option<u8>> {
+    grid.rows
+        .iter()
+        .flat_map(|row| row.cells.iter().map(|cell| cell.value))
+        .collect()
+}
+
+/// copy of `grid` with cell values (row by row) replaced by `values`
+pub(crate) fn with_values<const N: usize>(grid: &Grid<N>, values: &[u8]) -> Grid<N> {
+    let mut result = grid.clone();
+    for (i, value) in values.iter().enumerate() {
+        let coordinate = Coordinate((i / N) as u8, (i % N) as u8);
+        if result.get_cell(coordinate).value != Some(*value) {
+            result.set_cell_value(coordinate, Some(*value)).unwrap();
+        }
+    }
+    result
+}
@@ -0,0 +1,267 @@
+use super::cnf::Cnf;
+use super::{with_values, Solver};
+use crate::model::Grid;
+
+/// DPLL search over the CNF encoding of the grid
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sat;
+
+impl Solver for Sat {
+    fn solutions<const N: usize>(&self, grid: &Grid<N>, limit: usize) -> Vec<Grid<N>> {
+        let cnf = Cnf::from_grid(grid);
+        Dpll::new(&cnf)
+            .models(limit)
+            .iter()
+            .map(|model| with_values(grid, &cnf.decode::<N>(model)))
+            .collect()
+    }
+}
+
+struct Dpll<'a> {
+    clauses: &'a [Vec<i32>],
+    occurrences: Vec<Vec<usize>>, // clauses containing each literal, see `literal_index`
+    values: Vec<Option<bool>>,    // by variable, index 0 unused
+    trail: Vec<i32>,              // assigned literals in order
+    models: Vec<Vec<bool>>,
+    limit: usize,
+}
+
+impl<'a> Dpll<'a> {
+    fn new(cnf: &'a Cnf) -> Dpll<'a> {
+        let mut occurrences = vec![Vec::new(); 2 * (cnf.variables + 1)];
+        for (c, clause) in cnf.clauses.iter().enumerate() {
+            for literal in clause {
+                occurrences[literal_index(*literal)].push(c);
+            }
+        }
+
+        Dpll {
+            clauses: &cnf.clauses,
+            occurrences,
+            values: vec![None; cnf.variables + 1],
+            trail: Vec::new(),
+            models: Vec::new(),
+            limit: 0,
+        }
+    }
+
+    /// up to `limit` satisfying assignments, index 0 is variable 1
+    fn models(mut self, limit: usize) -> Vec<Vec<bool>> {
+        self.limit = limit;
+        if limit == 0 {
+            return self.models;
+        }
+
+        for c in 0..self.clauses.len() {
+            match self.clauses[c][..] {
+                [] => return self.models,
+                [literal] => match self.value(literal) {
+                    Some(false) => return self.models,
+                    Some(true) => {}
+                    None => self.assign(literal),
+                },
+                _ => {}
+            }
+        }
+
+        if self.propagate(0) {
+            self.search();
+        }
+        self.models
+    }
+
+    fn value(&self, literal: i32) -> Option<bool> {
+        self.values[literal.unsigned_abs() as usize].map(|value| value == (literal > 0))
+    }
+
+    fn assign(&mut self, literal: i32) {
+        self.values[literal.unsigned_abs() as usize] = Some(literal > 0);
+        self.trail.push(literal);
+    }
+
+    fn undo(&mut self, mark: usize) {
+        while self.trail.len() > mark {
+            let literal = self.trail.pop().unwrap();
+            self.values[literal.unsigned_abs() as usize] = None;
+        }
+    }
+
+    /// assign literals of unit clauses, false on conflict
+    fn propagate(&mut self, from: usize) -> bool {
+        let mut head = from;
+        while head < self.trail.len() {
+            let falsified = literal_index(-self.trail[head]);
+            head += 1;
+
+            for k in 0..self.occurrences[falsified].len() {
+                let clause = &self.clauses[self.occurrences[falsified][k]];
+                let mut unassigned: Option<i32> = None;
+                let mut unassigned_count = 0;
+                let mut satisfied = false;
+                for literal in clause {
+                    match self.value(*literal) {
+                        Some(true) => {
+                            satisfied = true;
+                            break;
+                        }
+                        Some(false) => {}
+                        None => {
+                            unassigned = Some(*literal);
+                            unassigned_count += 1;
+                        }
+                    }
+                }
+
+                if satisfied {
+                    continue;
+                }
+                match (unassigned_count, unassigned) {
+                    (0, _) => return false,
+                    (1, Some(literal)) => self.assign(literal),
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    /// an unassigned literal of the unsatisfied clause with the fewest of them
+    fn branch(&self) -> Option<i32> {
+        let mut best: Option<i32> = None;
+        let mut best_count = usize::MAX;
+        for clause in self.clauses {
+            if clause
+                .iter()
+                .any(|literal| self.value(*literal) == Some(true))
+            {
+                continue;
+            }
+            let mut unassigned = clause
+                .iter()
+                .filter(|literal| self.value(**literal).is_none());
+            let count = unassigned.clone().count();
+            if count < best_count {
+                best = unassigned.next().copied();
+                best_count = count;
+                if count <= 2 {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    /// returns true once `limit` models are found
+    fn search(&mut self) -> bool {
+        let literal = match self.branch() {
+            Some(literal) => literal,
+            None => {
+                // every clause is satisfied, unassigned variables are false
+                let model = self.values[1..]
+                    .iter()
+                    .map(|value| value.unwrap_or(false))
+                    .collect();
+                self.models.push(model);
+                return self.models.len() >= self.limit;
+            }
+        };
+
+        for literal in [literal, -literal] {
+            let mark = self.trail.len();
+            self.assign(literal);
+            if self.propagate(mark) && self.search() {
+                return true;
+            }
+            self.undo(mark);
+        }
+
+        false
+    }
+}
+
+fn literal_index(literal: i32) -> usize {
+    2 * literal.unsigned_abs() as usize + (literal < 0) as usize
+}
+
+#[cfg(all(test, feature = "sat"))]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+    use crate::solver::Backtracking;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    const SOLUTION: &str =
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    /// `line` with the cells at `indices` emptied
+    fn cleared(line: &str, indices: &[usize]) -> String {
+        let mut bytes: Vec<u8> = line.bytes().collect();
+        for &index in indices {
+            bytes[index] = b'.';
+        }
+        String::from_utf8(bytes).unwrap()
+    }
+
+    /// solutions found by `solver` as lines, sorted
+    fn lines<const N: usize, S: Solver>(solver: S, grid: &Grid<N>, limit: usize) -> Vec<String> {
+        let mut lines: Vec<String> = solver
+            .solutions(grid, limit)
+            .iter()
+            .map(|solution| format_line(solution).unwrap())
+            .collect();
+        lines.sort();
+        lines
+    }
+
+    #[test]
+    fn agrees_with_backtracking_on_known_counts() {
+        let cases = [
+            (String::from(PUZZLE), 1),
+            (format!("55{}", &PUZZLE[2..]), 0),
+            // r1c1 has no candidate left, r4c1 holds the 9
+            (format!(".12345678{}9{}", ".".repeat(18), ".".repeat(53)), 0),
+            (format!("{}..", &SOLUTION[..79]), 1),
+            // 4 and 8 can be swapped in r2c8, r2c9, r7c8 and r7c9
+            (cleared(SOLUTION, &[16, 17, 61, 62]), 2),
+        ];
+        for (line, count) in cases {
+            let grid: Grid<9> = parse_line(&line).unwrap();
+            assert_eq!(Backtracking.count_solutions(&grid, 10), count, "{}", line);
+            assert_eq!(
+                lines(Sat, &grid, 10),
+                lines(Backtracking, &grid, 10),
+                "{}",
+                line
+            );
+        }
+
+        // every 4x4 grid
+        let empty: Grid<4> = parse_line(&".".repeat(16)).unwrap();
+        assert_eq!(Sat.count_solutions(&empty, 1000), 288);
+        assert_eq!(lines(Sat, &empty, 1000), lines(Backtracking, &empty, 1000));
+    }
+
+    #[test]
+    fn agrees_with_backtracking_on_random_puzzles() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let mut cells: Vec<usize> = (0..81).collect();
+        for count in (20..65).step_by(3) {
+            cells.shuffle(&mut rng);
+            let line = cleared(SOLUTION, &cells[..count]);
+            let grid: Grid<9> = parse_line(&line).unwrap();
+
+            let solutions = Backtracking.count_solutions(&grid, 20);
+            assert_eq!(Sat.count_solutions(&grid, 20), solutions, "{}", line);
+            if solutions < 20 {
+                assert_eq!(
+                    lines(Sat, &grid, 20),
+                    lines(Backtracking, &grid, 20),
+                    "{}",
+                    line
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,400 @@
+use super::{Transform, Transformed};
+use crate::error::SudokuError;
+use crate::model::Grid;
+use std::cmp::Ordering;
+
+/// largest grid size for which the symmetry group is small enough to search exhaustively
+const MAX_CANONICAL_SIZE: usize = 9;
+
+/// a full row (or column) reordering that keeps bands (or stacks) intact
+struct LinePermutation {
+    groups: Vec<usize>,     // order of bands / stacks
+    lines: Vec<Vec<usize>>, // order of rows / columns within each new band / stack
+    map: Vec<usize>,        // old line of each new line
+}
+
+///
+/// Map `grid` to the minimal representative of its equivalence class under the sudoku
+/// symmetry group (transposition, band/stack/row/column permutations and relabeling).
+///
+/// Equivalent grids have the same canonical grid, the returned transform maps `grid` onto it.
+/// Cells are compared row by row with blanks first and values relabeled in order of appearance.
+///
+/// Column orders are enumerated, rows are then picked one at a time: only the rows giving the
+/// smallest next line are followed, and a branch is dropped once it compares above the best grid.
+///
+pub fn canonicalize<const N: usize>(grid: &Grid<N>) -> Result<Transformed<N>, SudokuError> {
+    if N > MAX_CANONICAL_SIZE {
+        return Err(SudokuError::UnsupportedGridSize(format!(
+            "Canonical form is only supported up to {}x{} grids",
+            MAX_CANONICAL_SIZE, MAX_CANONICAL_SIZE
+        )));
+    }
+
+    let sub_grid_size = (N as f64).sqrt() as usize;
+    let permutations = line_permutations(sub_grid_size);
+    let mut search = RowSearch {
+        size: N,
+        sub_grid_size,
+        cells: Vec::new(),
+        best: vec![usize::MAX; N * N],
+        best_rows: Vec::new(),
+        improved: false,
+    };
+    let mut best_choice = (false, 0, Vec::new());
+
+    for transposed in [false, true] {
+        // 0 for blank, value + 1 otherwise
+        let mut cells = vec![vec![0; N]; N];
+        for row in &grid.rows {
+            for cell in &row.cells {
+                let (r, c) = (
+                    cell.coordinate.row() as usize,
+                    cell.coordinate.col() as usize,
+                );
+                let (r, c) = if transposed { (c, r) } else { (r, c) };
+                cells[r][c] = cell.value.map_or(0, |v| v as usize + 1);
+            }
+        }
+
+        for (col_index, cols) in permutations.iter().enumerate() {
+            search.cells = cells
+                .iter()
+                .map(|line| cols.map.iter().map(|c| line[*c]).collect())
+                .collect();
+            search.improved = false;
+            search.extend(&mut Vec::new(), &vec![0; N + 1], 1);
+            if search.improved {
+                best_choice = (transposed, col_index, search.best_rows.clone());
+            }
+        }
+    }
+
+    let (transposed, col_index, row_map) = best_choice;
+    let rows = line_permutation(row_map, sub_grid_size);
+    let cols = &permutations[col_index];
+
+    let mut transforms: Vec<Transform> = Vec::new();
+    if transposed {
+        transforms.push(Transform::Transpose);
+    }
+    transforms.push(Transform::PermuteBands(rows.groups.clone()));
+    for (band, order) in rows.lines.iter().enumerate() {
+        transforms.push(Transform::PermuteRowsInBand(band, order.clone()));
+    }
+    transforms.push(Transform::PermuteStacks(cols.groups.clone()));
+    for (stack, order) in cols.lines.iter().enumerate() {
+        transforms.push(Transform::PermuteColsInStack(stack, order.clone()));
+    }
+
+    let moved = Transform::Compose(transforms.clone()).apply(grid)?.grid;
+    transforms.push(Transform::Relabel(relabel_by_appearance(&moved)));
+
+    Transform::Compose(transforms).apply(grid)
+}
+
+/// depth first search of the row order giving the smallest grid for fixed columns
+struct RowSearch {
+    size: usize,
+    sub_grid_size: usize,
+    cells: Vec<Vec<usize>>, // 0 for blank, value + 1 otherwise, columns already reordered
+    best: Vec<usize>,       // smallest labeled cells found so far, row by row
+    best_rows: Vec<usize>,  // old row of each new row of `best`
+    improved: bool,         // whether `best` was replaced since the columns changed
+}
+
+impl RowSearch {
+    /// `rows` holds the old row of each new row so far, `labels` the label of each value
+    fn extend(&mut self, rows: &mut Vec<usize>, labels: &[usize], next_label: usize) {
+        let (n, s) = (self.size, self.sub_grid_size);
+        let depth = rows.len();
+        if depth == n {
+            self.best_rows = rows.clone();
+            self.improved = true;
+            return;
+        }
+
+        // rows allowed next: the first row of an unused band, or an unused row of the current band
+        let offset = depth % s;
+        let candidates: Vec<usize> = if offset == 0 {
+            (0..n)
+                .filter(|r| rows.iter().all(|used| used / s != r / s))
+                .collect()
+        } else {
+            let band = rows[depth - offset] / s;
+            (band * s..band * s + s)
+                .filter(|r| !rows.contains(r))
+                .collect()
+        };
+
+        let mut lines: Vec<(usize, Vec<usize>, Vec<usize>, usize)> = candidates
+            .into_iter()
+            .map(|r| {
+                let mut labels = labels.to_vec();
+                let mut next_label = next_label;
+                let line: Vec<usize> = self.cells[r]
+                    .iter()
+                    .map(|value| {
+                        if *value != 0 && labels[*value] == 0 {
+                            labels[*value] = next_label;
+                            next_label += 1;
+                        }
+                        labels[*value]
+                    })
+                    .collect();
+                (r, line, labels, next_label)
+            })
+            .collect();
+        let smallest = lines
+            .iter()
+            .map(|(_, line, _, _)| line)
+            .min()
+            .unwrap()
+            .clone();
+        lines.retain(|(_, line, _, _)| *line == smallest);
+
+        for (r, line, labels, next_label) in lines {
+            // compared again as `best` may have improved in an earlier branch
+            let best_line = &mut self.best[depth * n..(depth + 1) * n];
+            match line.as_slice().cmp(best_line) {
+                Ordering::Greater => return,
+                Ordering::Less => {
+                    best_line.copy_from_slice(&line);
+                    self.best[(depth + 1) * n..].fill(usize::MAX);
+                }
+                Ordering::Equal => {}
+            }
+            rows.push(r);
+            self.extend(rows, &labels, next_label);
+            rows.pop();
+        }
+    }
+}
+
+/// new value of each value, numbered in order of first appearance (row by row)
+fn relabel_by_appearance<const N: usize>(grid: &Grid<N>) -> Vec<u8> {
+    let mut map: Vec<Option<u8>> = vec![None; N];
+    let mut next_label = 0;
+    for row in &grid.rows {
+        for cell in &row.cells {
+            if let Some(value) = cell.value {
+                if map[value as usize].is_none() {
+                    map[value as usize] = Some(next_label);
+                    next_label += 1;
+                }
+            }
+        }
+    }
+
+    // values that do not appear take the remaining labels
+    map.iter()
+        .map(|label| {
+            label.unwrap_or_else(|| {
+                next_label += 1;
+                next_label - 1
+            })
+        })
+        .collect()
+}
+
+/// band / stack order and line orders of a full reordering, given the old line of each new line
+fn line_permutation(map: Vec<usize>, sub_grid_size: usize) -> LinePermutation {
+    let groups = map
+        .chunks(sub_grid_size)
+        .map(|lines| lines[0] / sub_grid_size)
+        .collect();
+    let lines = map
+        .chunks(sub_grid_size)
+        .map(|lines| lines.iter().map(|line| line % sub_grid_size).collect())
+        .collect();
+    LinePermutation { groups, lines, map }
+}
+
+fn line_permutations(sub_grid_size: usize) -> Vec<LinePermutation> {
+    let orders = permutations(sub_grid_size);
+
+    // every combination of a group order and one line order per group
+    let mut combinations: Vec<(Vec<usize>, Vec<Vec<usize>>)> = orders
+        .iter()
+        .map(|groups| (groups.clone(), Vec::new()))
+        .collect();
+    for _ in 0..sub_grid_size {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|(groups, lines)| {
+                orders.iter().map(move |order| {
+                    let mut lines = lines.clone();
+                    lines.push(order.clone());
+                    (groups.clone(), lines)
+                })
+            })
+            .collect();
+    }
+
+    combinations
+        .into_iter()
+        .map(|(groups, lines)| {
+            let mut map = Vec::new();
+            for (group, order) in lines.iter().enumerate() {
+                for line in order {
+                    map.push(groups[group] * sub_grid_size + line);
+                }
+            }
+            LinePermutation { groups, lines, map }
+        })
+        .collect()
+}
+
+fn permutations(len: usize) -> Vec<Vec<usize>> {
+    if len == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for permutation in permutations(len - 1) {
+        for i in 0..len {
+            let mut next = permutation.clone();
+            next.insert(i, len - 1);
+            result.push(next);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_line, parse_line};
+    use crate::transform::Reflection;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const PUZZLES: [&str; 3] = [
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        "......5...13...9.27....5.1......2...3.6....4.2.5671...9...6.........41.5.3..9.8..",
+        "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+    ];
+
+    fn canonical<const N: usize>(grid: &Grid<N>) -> String {
+        format_line(&canonicalize(grid).unwrap().grid).unwrap()
+    }
+
+    #[test]
+    fn invariant_under_symmetries() {
+        let mut rng = StdRng::seed_from_u64(32);
+        for puzzle in PUZZLES {
+            let grid: Grid<9> = parse_line(puzzle).unwrap();
+            let expected = canonical(&grid);
+            for i in 0..6 {
+                let mut transforms = vec![Transform::shuffle::<9, _>(&mut rng)];
+                transforms.push(match i % 3 {
+                    0 => Transform::Rotate(i as u8 % 4),
+                    1 => Transform::Reflect(Reflection::AntiDiagonal),
+                    _ => Transform::Reflect(Reflection::LeftRight),
+                });
+                let moved = Transform::Compose(transforms).apply(&grid).unwrap().grid;
+                assert_eq!(canonical(&moved), expected, "{}", puzzle);
+            }
+        }
+    }
+
+    #[test]
+    fn transform_maps_onto_canonical() {
+        for puzzle in PUZZLES {
+            let grid: Grid<9> = parse_line(puzzle).unwrap();
+            let canonical = canonicalize(&grid).unwrap();
+            let moved = canonical.transform.apply(&grid).unwrap().grid;
+            assert_eq!(
+                format_line(&moved).unwrap(),
+                format_line(&canonical.grid).unwrap()
+            );
+            // already canonical grids stay as they are
+            assert_eq!(
+                format_line(&canonicalize(&canonical.grid).unwrap().grid).unwrap(),
+                format_line(&canonical.grid).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn known_canonical_forms() {
+        let grid: Grid<9> = parse_line(PUZZLES[2]).unwrap();
+        assert_eq!(
+            canonical(&grid),
+            "123456789457189263698273514271895346539764128864312957345928671786531492912647835"
+        );
+    }
+
+    /// minimal labeled cells over every transposition and row and column reordering
+    fn exhaustive<const N: usize>(grid: &Grid<N>) -> Vec<usize> {
+        let permutations = line_permutations((N as f64).sqrt() as usize);
+        let mut best = vec![usize::MAX; N * N];
+        for transposed in [false, true] {
+            for rows in &permutations {
+                for cols in &permutations {
+                    let mut labels = vec![0; N + 1];
+                    let mut next_label = 1;
+                    let cells: Vec<usize> = (0..N * N)
+                        .map(|i| {
+                            let (r, c) = (rows.map[i / N], cols.map[i % N]);
+                            let (r, c) = if transposed { (c, r) } else { (r, c) };
+                            let value = grid
+                                .get_cell(crate::model::Coordinate(r as u8, c as u8))
+                                .value
+                                .map_or(0, |v| v as usize + 1);
+                            if value != 0 && labels[value] == 0 {
+                                labels[value] = next_label;
+                                next_label += 1;
+                            }
+                            labels[value]
+                        })
+                        .collect();
+                    best = best.min(cells);
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn matches_exhaustive_search() {
+        let solution: Grid<4> = parse_line("1234341221434321").unwrap();
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..50 {
+            let mut grid = Transform::shuffle::<4, _>(&mut rng)
+                .apply(&solution)
+                .unwrap()
+                .grid;
+            for row in solution.rows.iter() {
+                for cell in row.cells.iter() {
+                    if rng.gen_bool(0.6) {
+                        grid.set_cell_value(cell.coordinate, None).unwrap();
+                    }
+                }
+            }
+
+            let expected: String = exhaustive(&grid)
+                .iter()
+                .map(|label| match label {
+                    0 => '.',
+                    label => char::from_digit(*label as u32, 10).unwrap(),
+                })
+                .collect();
+            assert_eq!(canonical(&grid), expected);
+        }
+    }
+
+    #[test]
+    fn distinguishes_inequivalent_grids() {
+        let first: Grid<9> = parse_line(PUZZLES[0]).unwrap();
+        let mut second = first.clone();
+        second
+            .set_cell_value(crate::model::Coordinate(0, 2), Some(3))
+            .unwrap();
+        assert_ne!(canonical(&first), canonical(&second));
+    }
+
+    #[test]
+    fn rejects_large_grids() {
+        assert!(canonicalize(&Grid::<16>::new()).is_err());
+    }
+}
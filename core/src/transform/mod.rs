@@ -0,0 +1,397 @@
+mod canonical;
+
+pub use canonical::canonicalize;
+
+use crate::error::SudokuError;
+use crate::model::{Coordinate, Grid};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Reflection {
+    LeftRight,    // mirror columns
+    TopBottom,    // mirror rows
+    AntiDiagonal, // mirror along the top-right to bottom-left diagonal
+}
+
+///
+/// Validity-preserving transform of a grid.
+/// Orders are 0-based and list which old row/col/band/stack takes each new position,
+/// i.e `order[new] = old`, while a relabeling maps each old value to its new one, `map[old] = new`.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum Transform {
+    Rotate(u8), // clockwise quarter turns
+    Reflect(Reflection),
+    Transpose,
+    Relabel(Vec<u8>), // new value of each old value
+    PermuteRowsInBand(usize, Vec<usize>),
+    PermuteColsInStack(usize, Vec<usize>),
+    PermuteBands(Vec<usize>),
+    PermuteStacks(Vec<usize>),
+    Compose(Vec<Transform>), // applied in order
+}
+
+/// result of a transform: the new grid and the transform that produced it
+pub struct Transformed<const N: usize> {
+    pub grid: Grid<N>,
+    pub transform: Transform,
+}
+
+impl Transform {
+    pub fn apply<const N: usize>(&self, grid: &Grid<N>) -> Result<Transformed<N>, SudokuError> {
+        self.validate::<N>()?;
+
+        Ok(Transformed {
+            grid: self.apply_unchecked(grid),
+            transform: self.clone(),
+        })
+    }
+
+    /// transform that undoes this one
+    pub fn inverse(&self) -> Transform {
+        match self {
+            Transform::Rotate(turns) => Transform::Rotate((4 - turns % 4) % 4),
+            Transform::Reflect(reflection) => Transform::Reflect(*reflection),
+            Transform::Transpose => Transform::Transpose,
+            Transform::Relabel(map) => {
+                let inverse = invert(&map.iter().map(|v| *v as usize).collect::<Vec<usize>>());
+                Transform::Relabel(inverse.iter().map(|v| *v as u8).collect())
+            }
+            Transform::PermuteRowsInBand(band, order) => {
+                Transform::PermuteRowsInBand(*band, invert(order))
+            }
+            Transform::PermuteColsInStack(stack, order) => {
+                Transform::PermuteColsInStack(*stack, invert(order))
+            }
+            Transform::PermuteBands(order) => Transform::PermuteBands(invert(order)),
+            Transform::PermuteStacks(order) => Transform::PermuteStacks(invert(order)),
+            Transform::Compose(transforms) => {
+                Transform::Compose(transforms.iter().rev().map(Transform::inverse).collect())
+            }
+        }
+    }
+
+    ///
+    /// Random composition of an optional transpose, a relabeling and
+    /// permutations of bands, stacks, rows within each band and columns within each stack
+    ///
+    pub fn shuffle<const N: usize, R: Rng>(rng: &mut R) -> Transform {
+        let sub_grid_size = (N as f64).sqrt() as usize;
+        let permutation = |rng: &mut R, len: usize| {
+            let mut order: Vec<usize> = (0..len).collect();
+            order.shuffle(rng);
+            order
+        };
+
+        let mut transforms: Vec<Transform> = Vec::new();
+        if rng.gen_bool(0.5) {
+            transforms.push(Transform::Transpose);
+        }
+        let mut values: Vec<u8> = (0..N as u8).collect();
+        values.shuffle(rng);
+        transforms.push(Transform::Relabel(values));
+        transforms.push(Transform::PermuteBands(permutation(rng, sub_grid_size)));
+        transforms.push(Transform::PermuteStacks(permutation(rng, sub_grid_size)));
+        for i in 0..sub_grid_size {
+            transforms.push(Transform::PermuteRowsInBand(
+                i,
+                permutation(rng, sub_grid_size),
+            ));
+            transforms.push(Transform::PermuteColsInStack(
+                i,
+                permutation(rng, sub_grid_size),
+            ));
+        }
+
+        Transform::Compose(transforms)
+    }
+}
+
+// private
+impl Transform {
+    fn validate<const N: usize>(&self) -> Result<(), SudokuError> {
+        let sub_grid_size = (N as f64).sqrt() as usize;
+        let invalid = |message: String| Err(SudokuError::InvalidFormat(message));
+
+        match self {
+            Transform::Rotate(_) | Transform::Reflect(_) | Transform::Transpose => Ok(()),
+            Transform::Relabel(map) => {
+                let map: Vec<usize> = map.iter().map(|v| *v as usize).collect();
+                if !is_permutation(&map, N) {
+                    return invalid(format!("Relabel {:?} is not a permutation of values", map));
+                }
+                Ok(())
+            }
+            Transform::PermuteRowsInBand(index, order)
+            | Transform::PermuteColsInStack(index, order) => {
+                if *index >= sub_grid_size || !is_permutation(order, sub_grid_size) {
+                    return invalid(format!("{:?} is invalid for a {}x{} grid", self, N, N));
+                }
+                Ok(())
+            }
+            Transform::PermuteBands(order) | Transform::PermuteStacks(order) => {
+                if !is_permutation(order, sub_grid_size) {
+                    return invalid(format!("{:?} is invalid for a {}x{} grid", self, N, N));
+                }
+                Ok(())
+            }
+            Transform::Compose(transforms) => {
+                for transform in transforms {
+                    transform.validate::<N>()?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn apply_unchecked<const N: usize>(&self, grid: &Grid<N>) -> Grid<N> {
+        if let Transform::Compose(transforms) = self {
+            let mut result = grid.clone();
+            for transform in transforms {
+                result = transform.apply_unchecked(&result);
+            }
+            return result;
+        }
+
+        let mut result: Grid<N> = Grid::new();
+        for row in &grid.rows {
+            for cell in &row.cells {
+                let coordinate = self.map_coordinate::<N>(cell.coordinate);
+                let value = cell.value.map(|v| self.map_value(v));
+                result.set_cell_value(coordinate, value).unwrap();
+                result.set_cell_editable(coordinate, cell.editable).unwrap();
+            }
+        }
+        result
+    }
+
+    /// new coordinate of the cell at `coordinate`
+    fn map_coordinate<const N: usize>(&self, coordinate: Coordinate) -> Coordinate {
+        let n = N - 1;
+        let sub_grid_size = (N as f64).sqrt() as usize;
+        let (r, c) = (coordinate.row() as usize, coordinate.col() as usize);
+        let moved = |order: &Vec<usize>, i: usize| order.iter().position(|o| *o == i).unwrap();
+
+        let (r, c) = match self {
+            Transform::Rotate(turns) => match turns % 4 {
+                1 => (c, n - r),
+                2 => (n - r, n - c),
+                3 => (n - c, r),
+                _ => (r, c),
+            },
+            Transform::Reflect(Reflection::LeftRight) => (r, n - c),
+            Transform::Reflect(Reflection::TopBottom) => (n - r, c),
+            Transform::Reflect(Reflection::AntiDiagonal) => (n - c, n - r),
+            Transform::Transpose => (c, r),
+            Transform::PermuteRowsInBand(band, order) if r / sub_grid_size == *band => {
+                (band * sub_grid_size + moved(order, r % sub_grid_size), c)
+            }
+            Transform::PermuteColsInStack(stack, order) if c / sub_grid_size == *stack => {
+                (r, stack * sub_grid_size + moved(order, c % sub_grid_size))
+            }
+            Transform::PermuteBands(order) => (
+                moved(order, r / sub_grid_size) * sub_grid_size + r % sub_grid_size,
+                c,
+            ),
+            Transform::PermuteStacks(order) => (
+                r,
+                moved(order, c / sub_grid_size) * sub_grid_size + c % sub_grid_size,
+            ),
+            _ => (r, c),
+        };
+
+        Coordinate(r as u8, c as u8)
+    }
+
+    fn map_value(&self, value: u8) -> u8 {
+        match self {
+            Transform::Relabel(map) => map[value as usize],
+            _ => value,
+        }
+    }
+}
+
+impl std::fmt::Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let one_based = |order: &Vec<usize>| {
+            order
+                .iter()
+                .map(|i| (i + 1).to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+
+        match self {
+            Transform::Rotate(turns) => write!(f, "rotate {}° clockwise", (turns % 4) as u16 * 90),
+            Transform::Reflect(Reflection::LeftRight) => write!(f, "mirror left-right"),
+            Transform::Reflect(Reflection::TopBottom) => write!(f, "mirror top-bottom"),
+            Transform::Reflect(Reflection::AntiDiagonal) => write!(f, "mirror anti-diagonal"),
+            Transform::Transpose => write!(f, "transpose"),
+            Transform::Relabel(map) => {
+                let pairs: Vec<String> = map
+                    .iter()
+                    .enumerate()
+                    .map(|(from, to)| format!("{}→{}", from + 1, to + 1))
+                    .collect();
+                write!(f, "relabel {}", pairs.join(" "))
+            }
+            Transform::PermuteRowsInBand(band, order) => {
+                write!(f, "reorder rows of band {}: {}", band + 1, one_based(order))
+            }
+            Transform::PermuteColsInStack(stack, order) => {
+                write!(
+                    f,
+                    "reorder columns of stack {}: {}",
+                    stack + 1,
+                    one_based(order)
+                )
+            }
+            Transform::PermuteBands(order) => write!(f, "reorder bands: {}", one_based(order)),
+            Transform::PermuteStacks(order) => write!(f, "reorder stacks: {}", one_based(order)),
+            Transform::Compose(transforms) => {
+                let steps: Vec<String> = transforms.iter().map(|t| t.to_string()).collect();
+                write!(f, "{}", steps.join(", then "))
+            }
+        }
+    }
+}
+
+fn is_permutation(order: &[usize], len: usize) -> bool {
+    let mut seen = vec![false; len];
+    order.len() == len
+        && order
+            .iter()
+            .all(|i| *i < len && !std::mem::replace(&mut seen[*i], true))
+}
+
+fn invert(order: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; order.len()];
+    for (i, o) in order.iter().enumerate() {
+        inverse[*o] = i;
+    }
+    inverse
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parse_line;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    /// puzzle with an entry at r1c3, so editability is checked too
+    fn example() -> Grid<9> {
+        let mut grid: Grid<9> = parse_line(PUZZLE).unwrap();
+        grid.set_cell_value(Coordinate(0, 2), Some(3)).unwrap();
+        grid
+    }
+
+    fn cells<const N: usize>(grid: &Grid<N>) -> Vec<(Option<u8>, bool)> {
+        grid.rows
+            .iter()
+            .flat_map(|row| row.cells.iter())
+            .map(|cell| (cell.value, cell.editable))
+            .collect()
+    }
+
+    fn variants() -> Vec<Transform> {
+        let mut transforms = vec![
+            Transform::Rotate(0),
+            Transform::Rotate(1),
+            Transform::Rotate(2),
+            Transform::Rotate(3),
+            Transform::Reflect(Reflection::LeftRight),
+            Transform::Reflect(Reflection::TopBottom),
+            Transform::Reflect(Reflection::AntiDiagonal),
+            Transform::Transpose,
+            Transform::Relabel(vec![3, 8, 0, 5, 1, 7, 2, 6, 4]),
+            Transform::PermuteRowsInBand(1, vec![2, 0, 1]),
+            Transform::PermuteColsInStack(2, vec![1, 2, 0]),
+            Transform::PermuteBands(vec![1, 2, 0]),
+            Transform::PermuteStacks(vec![2, 0, 1]),
+        ];
+        transforms.push(Transform::Compose(transforms.clone()));
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..10 {
+            transforms.push(Transform::shuffle::<9, _>(&mut rng));
+        }
+        transforms
+    }
+
+    #[test]
+    fn inverse_undoes_every_variant() {
+        let grid = example();
+        for transform in variants() {
+            let transformed = transform.apply(&grid).unwrap().grid;
+            let restored = transform.inverse().apply(&transformed).unwrap().grid;
+            assert_eq!(cells(&restored), cells(&grid), "{:?}", transform);
+        }
+    }
+
+    #[test]
+    fn orders_list_old_positions() {
+        let grid = example();
+        let at = |grid: &Grid<9>, r: u8, c: u8| grid.get_cell(Coordinate(r, c)).value;
+
+        // new band 0 is old band 1
+        let moved = Transform::PermuteBands(vec![1, 2, 0])
+            .apply(&grid)
+            .unwrap()
+            .grid;
+        assert_eq!(at(&moved, 0, 0), at(&grid, 3, 0));
+        // new row 0 of band 1 is its old row 2
+        let moved = Transform::PermuteRowsInBand(1, vec![2, 0, 1])
+            .apply(&grid)
+            .unwrap()
+            .grid;
+        assert_eq!(at(&moved, 3, 0), at(&grid, 5, 0));
+        // new column 0 is old column 6
+        let moved = Transform::PermuteStacks(vec![2, 0, 1])
+            .apply(&grid)
+            .unwrap()
+            .grid;
+        assert_eq!(at(&moved, 0, 0), at(&grid, 0, 6));
+    }
+
+    #[test]
+    fn relabel_maps_old_values() {
+        let grid = example();
+        let moved = Transform::Relabel(vec![3, 8, 0, 5, 1, 7, 2, 6, 4])
+            .apply(&grid)
+            .unwrap()
+            .grid;
+        // r1c1 holds a 5 (value 4), which becomes value 1
+        assert_eq!(moved.get_cell(Coordinate(0, 0)).value, Some(1));
+    }
+
+    #[test]
+    fn rotation_is_clockwise() {
+        let grid = example();
+        let turned = Transform::Rotate(1).apply(&grid).unwrap().grid;
+        // the top left corner moves to the top right
+        assert_eq!(
+            turned.get_cell(Coordinate(0, 8)).value,
+            grid.get_cell(Coordinate(0, 0)).value
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_orders() {
+        let grid = example();
+        for transform in [
+            Transform::PermuteBands(vec![0, 0, 1]),
+            Transform::PermuteRowsInBand(3, vec![0, 1, 2]),
+            Transform::Relabel(vec![0, 1, 2]),
+            Transform::Compose(vec![
+                Transform::Transpose,
+                Transform::PermuteStacks(vec![0, 1]),
+            ]),
+        ] {
+            assert!(transform.apply(&grid).is_err(), "{:?}", transform);
+        }
+    }
+}